@@ -1,6 +1,7 @@
 use std::cmp::max;
-use std::collections::HashMap;
 use std::ops::{Deref, DerefMut};
+#[cfg(unix)]
+use std::os::unix::io::RawFd;
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -12,10 +13,13 @@ use toml::Value;
 
 use alacritty_terminal::tty::Options as PtyOptions;
 
+#[cfg(unix)]
+use crate::config::Signal;
 use crate::config::UiConfig;
 use crate::config::ui_config::Program;
 use crate::config::window::{Class, Identity};
 use crate::logging::LOG_TARGET_IPC_CONFIG;
+use crate::pty_backend::DEFAULT_BACKEND;
 
 /// CLI options for the main Alacritty executable.
 #[derive(Parser, Default, Debug)]
@@ -66,6 +70,17 @@ pub struct Options {
     #[clap(long)]
     pub daemon: bool,
 
+    /// Attach the initial window to the file descriptor of an existing terminal, like a PTY
+    /// handed over by a supervisor process, instead of spawning a shell.
+    #[cfg(unix)]
+    #[clap(
+        long,
+        value_name = "FD",
+        value_parser = parse_attach_fd,
+        conflicts_with_all = ["daemon", "serial", "command"]
+    )]
+    pub attach_fd: Option<RawFd>,
+
     /// CLI options for config overrides.
     #[clap(skip)]
     pub config_options: ParsedOptions,
@@ -151,6 +166,33 @@ fn parse_class(input: &str) -> Result<Class, String> {
     Ok(Class::new(general, instance))
 }
 
+/// Parse an environment variable assignment like `KEY=VAL`.
+fn parse_env(input: &str) -> Result<(String, String), String> {
+    match input.split_once('=') {
+        Some(("", _)) => Err(String::from("Missing variable name")),
+        Some((key, value)) => Ok((key.into(), value.into())),
+        None => Err(String::from("Expected KEY=VAL")),
+    }
+}
+
+/// Parse a file descriptor which can be attached to.
+#[cfg(unix)]
+fn parse_attach_fd(input: &str) -> Result<RawFd, String> {
+    let fd: RawFd = input.parse().map_err(|_| String::from("Invalid file descriptor"))?;
+
+    // Alacritty's own stdio must never be owned by a terminal.
+    if (0..=2).contains(&fd) {
+        return Err(String::from("Standard input, output and error can't be attached"));
+    }
+
+    // SAFETY: `F_GETFD` only checks the file descriptor without modifying it.
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+        return Err(format!("File descriptor is not open: {}", std::io::Error::last_os_error()));
+    }
+
+    Ok(fd)
+}
+
 /// Convert to hex if possible, else decimal
 fn parse_hex_or_decimal(input: &str) -> Option<u32> {
     input
@@ -170,6 +212,35 @@ pub struct TerminalOptions {
     #[clap(long)]
     pub hold: bool,
 
+    /// Set an environment variable for the shell [example: 'KUBECONFIG=/home/user/.kube/dev'].
+    #[clap(long, value_name = "KEY=VAL", value_parser = parse_env)]
+    pub env: Vec<(String, String)>,
+
+    /// Use a serial device as terminal instead of spawning a shell.
+    #[cfg(unix)]
+    #[clap(long, value_hint = ValueHint::FilePath, conflicts_with = "command")]
+    pub serial: Option<PathBuf>,
+
+    /// Baud rate of the serial device [default: 115200].
+    #[cfg(unix)]
+    #[clap(long, requires = "serial")]
+    baud: Option<u32>,
+
+    /// File descriptor of an existing terminal, which can only be passed through the CLI.
+    #[cfg(unix)]
+    #[clap(skip)]
+    #[serde(skip)]
+    pub attach_fd: Option<RawFd>,
+
+    /// Connect the terminal to a Unix socket path or TCP address instead of spawning a shell.
+    #[cfg(unix)]
+    #[clap(long, value_name = "ADDRESS", conflicts_with_all = ["serial", "command"])]
+    pub connect: Option<String>,
+
+    /// Name of the backend providing the terminal I/O [default: shell].
+    #[clap(long, value_name = "NAME")]
+    pub backend: Option<String>,
+
     /// Command and args to execute (must be last argument).
     #[clap(short = 'e', long, allow_hyphen_values = true, num_args = 1..)]
     command: Vec<String>,
@@ -182,6 +253,30 @@ impl TerminalOptions {
         Some(Program::WithArgs { program: program.clone(), args: args.to_vec() })
     }
 
+    /// Baud rate of the serial device.
+    #[cfg(unix)]
+    pub fn baud(&self) -> u32 {
+        self.baud.unwrap_or(115_200)
+    }
+
+    /// Name of the PTY backend selected for the terminal.
+    pub fn backend_name(&self) -> &str {
+        if let Some(backend) = &self.backend {
+            return backend;
+        }
+
+        #[cfg(unix)]
+        if self.attach_fd.is_some() {
+            return "fd";
+        } else if self.serial.is_some() {
+            return "serial";
+        } else if self.connect.is_some() {
+            return "socket";
+        }
+
+        DEFAULT_BACKEND
+    }
+
     /// Override the [`PtyOptions`]'s fields with the [`TerminalOptions`].
     pub fn override_pty_config(&self, pty_config: &mut PtyOptions) {
         if let Some(working_directory) = &self.working_directory {
@@ -197,6 +292,7 @@ impl TerminalOptions {
         }
 
         pty_config.drain_on_exit |= self.hold;
+        pty_config.env.extend(self.env.iter().cloned());
     }
 }
 
@@ -206,7 +302,7 @@ impl From<TerminalOptions> for PtyOptions {
             working_directory: options.working_directory.take(),
             shell: options.command().map(Into::into),
             drain_on_exit: options.hold,
-            env: HashMap::new(),
+            env: options.env.into_iter().collect(),
             #[cfg(target_os = "windows")]
             escape_args: false,
         }
@@ -263,13 +359,16 @@ pub struct MessageOptions {
 #[derive(Subcommand, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum SocketMessage {
     /// Create a new window in the same Alacritty process.
-    CreateWindow(WindowOptions),
+    CreateWindow(Box<WindowOptions>),
 
     /// Update the Alacritty configuration.
     Config(IpcConfig),
 
     /// Read runtime Alacritty configuration.
     GetConfig(IpcGetConfig),
+
+    /// Send a signal to the foreground process of a terminal.
+    Signal(IpcSignal),
 }
 
 /// Migrate the configuration file.
@@ -359,6 +458,20 @@ pub struct IpcGetConfig {
     pub window_id: Option<i128>,
 }
 
+/// Parameters to the `signal` IPC subcommand.
+#[cfg(unix)]
+#[derive(Args, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct IpcSignal {
+    /// Signal name [example: 'INT', 'SIGTERM'].
+    pub signal: Signal,
+
+    /// Window ID receiving the signal.
+    ///
+    /// Use `-1` to send the signal to all windows.
+    #[clap(short, long, allow_hyphen_values = true, env = "ALACRITTY_WINDOW_ID")]
+    pub window_id: i128,
+}
+
 /// Parsed CLI config overrides.
 #[derive(Debug, Default)]
 pub struct ParsedOptions {
@@ -517,6 +630,33 @@ mod tests {
         assert_eq!(class.instance, "two");
     }
 
+    #[test]
+    fn parse_env_assignment() {
+        let env = parse_env("KUBECONFIG=/tmp/a=b").unwrap();
+        assert_eq!(env, ("KUBECONFIG".into(), "/tmp/a=b".into()));
+        assert_eq!(parse_env("EMPTY=").unwrap(), ("EMPTY".into(), String::new()));
+        assert!(parse_env("KUBECONFIG").is_err());
+        assert!(parse_env("=value").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn parse_attach_fds() {
+        use std::os::unix::io::AsRawFd;
+
+        let file = std::fs::File::open("/dev/null").unwrap();
+        let fd = file.as_raw_fd();
+        assert_eq!(parse_attach_fd(&fd.to_string()), Ok(fd));
+
+        assert!(parse_attach_fd("0").is_err());
+        assert!(parse_attach_fd("2").is_err());
+        assert!(parse_attach_fd("-1").is_err());
+        assert!(parse_attach_fd("fd").is_err());
+
+        // File descriptors which aren't open.
+        assert!(parse_attach_fd("1000000").is_err());
+    }
+
     #[test]
     fn parse_invalid_class() {
         let class = parse_class("one,two,three");
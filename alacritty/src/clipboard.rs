@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use log::{debug, warn};
 use winit::raw_window_handle::RawDisplayHandle;
 
@@ -12,9 +14,15 @@ use copypasta::wayland_clipboard;
 #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
 use copypasta::x11_clipboard::{Primary as X11SelectionClipboard, X11ClipboardContext};
 
+/// Maximum number of entries in the clipboard history.
+const MAX_HISTORY: usize = 50;
+
 pub struct Clipboard {
     clipboard: Box<dyn ClipboardProvider>,
     selection: Option<Box<dyn ClipboardProvider>>,
+
+    /// Text stored in any clipboard, most recent first.
+    history: VecDeque<String>,
 }
 
 impl Clipboard {
@@ -25,7 +33,11 @@ impl Clipboard {
                 let (selection, clipboard) = unsafe {
                     wayland_clipboard::create_clipboards_from_external(display.display.as_ptr())
                 };
-                Self { clipboard: Box::new(clipboard), selection: Some(Box::new(selection)) }
+                Self {
+                    clipboard: Box::new(clipboard),
+                    selection: Some(Box::new(selection)),
+                    history: Default::default(),
+                }
             },
             _ => Self::default(),
         }
@@ -34,19 +46,28 @@ impl Clipboard {
     /// Used for tests, to handle missing clipboard provider when built without the `x11`
     /// feature, and as default clipboard value.
     pub fn new_nop() -> Self {
-        Self { clipboard: Box::new(NopClipboardContext::new().unwrap()), selection: None }
+        Self {
+            clipboard: Box::new(NopClipboardContext::new().unwrap()),
+            selection: None,
+            history: Default::default(),
+        }
     }
 }
 
 impl Default for Clipboard {
     fn default() -> Self {
         #[cfg(any(target_os = "macos", windows))]
-        return Self { clipboard: Box::new(ClipboardContext::new().unwrap()), selection: None };
+        return Self {
+            clipboard: Box::new(ClipboardContext::new().unwrap()),
+            selection: None,
+            history: Default::default(),
+        };
 
         #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
         return Self {
             clipboard: Box::new(ClipboardContext::new().unwrap()),
             selection: Some(Box::new(X11ClipboardContext::<X11SelectionClipboard>::new().unwrap())),
+            history: Default::default(),
         };
 
         #[cfg(not(any(feature = "x11", target_os = "macos", windows)))]
@@ -56,13 +77,16 @@ impl Default for Clipboard {
 
 impl Clipboard {
     pub fn store(&mut self, ty: ClipboardType, text: impl Into<String>) {
+        let text = text.into();
+        self.add_to_history(&text);
+
         let clipboard = match (ty, &mut self.selection) {
             (ClipboardType::Selection, Some(provider)) => provider,
             (ClipboardType::Selection, None) => return,
             _ => &mut self.clipboard,
         };
 
-        clipboard.set_contents(text.into()).unwrap_or_else(|err| {
+        clipboard.set_contents(text).unwrap_or_else(|err| {
             warn!("Unable to store text in clipboard: {err}");
         });
     }
@@ -81,4 +105,41 @@ impl Clipboard {
             Ok(text) => text,
         }
     }
+
+    /// Text previously stored in any clipboard, most recent first.
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
+    }
+
+    fn add_to_history(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+
+        // Move duplicates to the front instead of storing them again.
+        self.history.retain(|entry| entry != text);
+        self.history.push_front(text.into());
+        self.history.truncate(MAX_HISTORY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history() {
+        let mut clipboard = Clipboard::new_nop();
+        clipboard.store(ClipboardType::Clipboard, "a");
+        clipboard.store(ClipboardType::Selection, "b");
+        clipboard.store(ClipboardType::Clipboard, "");
+        clipboard.store(ClipboardType::Clipboard, "a");
+        assert_eq!(clipboard.history().collect::<Vec<_>>(), ["a", "b"]);
+
+        for i in 0..MAX_HISTORY {
+            clipboard.store(ClipboardType::Clipboard, i.to_string());
+        }
+        assert_eq!(clipboard.history().count(), MAX_HISTORY);
+        assert_eq!(clipboard.history().next(), Some("49"));
+    }
 }
@@ -1,10 +1,11 @@
 #![allow(clippy::enum_glob_use)]
 
 use std::fmt::{self, Debug, Display};
+use std::str::FromStr;
 
 use bitflags::bitflags;
 use serde::de::{self, Error as SerdeError, MapAccess, Unexpected, Visitor};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::rc::Rc;
 use toml::Value as SerdeValue;
 use winit::event::MouseButton;
@@ -37,17 +38,23 @@ pub struct Binding<T> {
     /// Excluded binding modes where the binding won't be activated.
     pub notmode: BindingMode,
 
+    /// User-defined mode required to activate binding.
+    pub user_mode: Option<String>,
+
     /// This property is used as part of the trigger detection code.
     ///
     /// For example, this might be a key like "G", or a mouse button.
     pub trigger: T,
+
+    /// Inputs which must follow the trigger to complete a multi-key binding.
+    pub sequence: Vec<(T, ModifiersState)>,
 }
 
 /// Bindings that are triggered by a keyboard key.
 pub type KeyBinding = Binding<BindingKey>;
 
-/// Bindings that are triggered by a mouse button.
-pub type MouseBinding = Binding<MouseButton>;
+/// Bindings that are triggered by a mouse button, the mouse wheel or a drag gesture.
+pub type MouseBinding = Binding<MouseTrigger>;
 
 impl<T: Eq> Binding<T> {
     #[inline]
@@ -57,14 +64,32 @@ impl<T: Eq> Binding<T> {
         // checks to be short circuited.
         self.trigger == *input
             && self.mods == mods
-            && mode.contains(self.mode)
-            && !mode.intersects(self.notmode)
+            && self.sequence.is_empty()
+            && self.is_active(mode)
+    }
+
+    /// Check if the binding is available in the current mode.
+    #[inline]
+    pub fn is_active(&self, mode: BindingMode) -> bool {
+        mode.contains(self.mode) && !mode.intersects(self.notmode)
+    }
+
+    /// Check if the binding belongs to the active user-defined mode.
+    ///
+    /// Bindings without a user-defined mode are only available outside of them.
+    #[inline]
+    pub fn in_user_mode(&self, user_mode: Option<&str>) -> bool {
+        self.user_mode.as_deref() == user_mode
     }
 
     #[inline]
     pub fn triggers_match(&self, binding: &Binding<T>) -> bool {
         // Check the binding's key and modifiers.
-        if self.trigger != binding.trigger || self.mods != binding.mods {
+        if self.trigger != binding.trigger
+            || self.mods != binding.mods
+            || self.sequence != binding.sequence
+            || self.user_mode != binding.user_mode
+        {
             return false;
         }
 
@@ -115,12 +140,48 @@ pub enum Action {
     #[config(skip)]
     Mouse(MouseAction),
 
+    /// Enter a user-defined binding mode.
+    #[config(skip)]
+    EnterMode(String),
+
+    /// Leave the active user-defined binding mode.
+    ExitMode,
+
+    /// Send a signal to the foreground process.
+    #[config(skip)]
+    Signal(Signal),
+
+    /// Execute multiple actions in order.
+    #[config(skip)]
+    Sequence(Vec<Action>),
+
+    /// Start recording keyboard input, or stop the active recording.
+    RecordMacro,
+
+    /// Send the recorded keyboard input to the terminal.
+    PlayMacro,
+
     /// Paste contents of system clipboard.
     Paste,
 
+    /// Paste contents of system clipboard after transforming them.
+    #[config(skip)]
+    PasteWith(PasteOptions),
+
+    /// Paste the given text.
+    #[config(skip)]
+    PasteText(String),
+
+    /// Open a picker for pasting text from the clipboard history.
+    ClipboardHistory,
+
     /// Store current selection into clipboard.
     Copy,
 
+    /// Store current selection into clipboard after transforming it.
+    #[config(skip)]
+    CopyWith(CopyOptions),
+
     /// Store current selection into selection buffer.
     CopySelection,
 
@@ -181,6 +242,9 @@ pub enum Action {
     /// Spawn a new instance of Alacritty.
     SpawnNewInstance,
 
+    /// Replace the shell with a new one, keeping the window and scrollback.
+    RespawnShell,
+
     /// Select next tab.
     SelectNextTab,
 
@@ -247,6 +311,9 @@ pub enum Action {
     /// Start a backward buffer search.
     SearchBackward,
 
+    /// Open the command palette.
+    CommandPalette,
+
     /// No action.
     None,
 }
@@ -288,6 +355,9 @@ impl Display for Action {
             Action::ViMotion(motion) => motion.fmt(f),
             Action::Vi(action) => action.fmt(f),
             Action::Mouse(action) => action.fmt(f),
+            Action::CopyWith(options) => write!(f, "Copy {options}"),
+            Action::PasteWith(options) => write!(f, "Paste {options}"),
+            Action::Signal(signal) => write!(f, "Signal {signal}"),
             _ => write!(f, "{self:?}"),
         }
     }
@@ -332,6 +402,20 @@ pub enum ViAction {
     SemanticSearchForward,
     /// Search backward for selection or word under the cursor.
     SemanticSearchBackward,
+    /// Copy the selection, or start the yank operator without a selection.
+    Yank,
+    /// Set a mark at the vi mode cursor, named by the next key.
+    SetMark,
+    /// Jump to the mark named by the next key.
+    JumpToMark,
+    /// Jump to the first occupied cell in the line of the mark named by the next key.
+    JumpToMarkLine,
+    /// Jump to the previous position in the jump list.
+    JumpBackward,
+    /// Jump to the next position in the jump list.
+    JumpForward,
+    /// Prompt for a line number to jump to.
+    GotoLine,
 }
 
 /// Search mode specific actions.
@@ -356,6 +440,181 @@ pub enum SearchAction {
     SearchHistoryNext,
 }
 
+/// Transformations applied to the selection when copying it.
+#[derive(Deserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CopyOptions {
+    /// Remove trailing whitespace from every line.
+    pub trim: bool,
+
+    /// Remove the leading whitespace shared by all lines.
+    pub dedent: bool,
+
+    /// Join all lines with a single space.
+    pub single_line: bool,
+}
+
+impl CopyOptions {
+    /// Apply the transformations to the copied text.
+    pub fn apply(self, text: &str) -> String {
+        let mut lines: Vec<&str> = text.split('\n').collect();
+
+        if self.trim {
+            lines.iter_mut().for_each(|line| *line = line.trim_end());
+        }
+
+        if self.dedent {
+            let indent = lines
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start_matches([' ', '\t']).len())
+                .min()
+                .unwrap_or(0);
+
+            for line in &mut lines {
+                let line_indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+                *line = &line[indent.min(line_indent)..];
+            }
+        }
+
+        if self.single_line {
+            let words = lines.iter().map(|line| line.trim()).filter(|line| !line.is_empty());
+            return words.collect::<Vec<_>>().join(" ");
+        }
+
+        lines.join("\n")
+    }
+}
+
+impl Display for CopyOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options =
+            [("trim", self.trim), ("dedent", self.dedent), ("single_line", self.single_line)];
+        fmt_options(f, &options)
+    }
+}
+
+/// Transformations applied to the clipboard contents when pasting them.
+#[derive(Deserialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PasteOptions {
+    /// Remove all line breaks.
+    pub strip_newlines: bool,
+
+    /// Join all lines with a single space.
+    pub single_line: bool,
+
+    /// Quote the text as a single shell argument.
+    pub shell_quote: bool,
+}
+
+impl PasteOptions {
+    /// Apply the transformations to the pasted text.
+    pub fn apply(self, text: &str) -> String {
+        let mut text = if self.single_line {
+            CopyOptions { single_line: true, ..Default::default() }.apply(text)
+        } else if self.strip_newlines {
+            text.replace(['\r', '\n'], "")
+        } else {
+            text.into()
+        };
+
+        if self.shell_quote {
+            text = format!("'{}'", text.replace('\'', "'\\''"));
+        }
+
+        text
+    }
+}
+
+impl Display for PasteOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = [
+            ("strip_newlines", self.strip_newlines),
+            ("single_line", self.single_line),
+            ("shell_quote", self.shell_quote),
+        ];
+        fmt_options(f, &options)
+    }
+}
+
+/// Format the names of all enabled options.
+fn fmt_options(f: &mut fmt::Formatter<'_>, options: &[(&str, bool)]) -> fmt::Result {
+    let enabled: Vec<_> =
+        options.iter().filter(|(_, enabled)| *enabled).map(|(name, _)| *name).collect();
+    write!(f, "{{ {} }}", enabled.join(", "))
+}
+
+/// Signal sent to the foreground process of the terminal.
+#[derive(Serialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Signal {
+    Hup,
+    Int,
+    Quit,
+    Kill,
+    Usr1,
+    Usr2,
+    Term,
+    Cont,
+    Stop,
+    Tstp,
+}
+
+impl Signal {
+    /// Get the platform's number of the signal.
+    #[cfg(unix)]
+    pub fn number(self) -> libc::c_int {
+        match self {
+            Self::Hup => libc::SIGHUP,
+            Self::Int => libc::SIGINT,
+            Self::Quit => libc::SIGQUIT,
+            Self::Kill => libc::SIGKILL,
+            Self::Usr1 => libc::SIGUSR1,
+            Self::Usr2 => libc::SIGUSR2,
+            Self::Term => libc::SIGTERM,
+            Self::Cont => libc::SIGCONT,
+            Self::Stop => libc::SIGSTOP,
+            Self::Tstp => libc::SIGTSTP,
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let upper = name.to_uppercase();
+        match upper.strip_prefix("SIG").unwrap_or(&upper) {
+            "HUP" => Ok(Self::Hup),
+            "INT" => Ok(Self::Int),
+            "QUIT" => Ok(Self::Quit),
+            "KILL" => Ok(Self::Kill),
+            "USR1" => Ok(Self::Usr1),
+            "USR2" => Ok(Self::Usr2),
+            "TERM" => Ok(Self::Term),
+            "CONT" => Ok(Self::Cont),
+            "STOP" => Ok(Self::Stop),
+            "TSTP" => Ok(Self::Tstp),
+            _ => Err(format!("unknown signal `{name}`")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Signal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(StringVisitor)?.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SIG{}", format!("{self:?}").to_uppercase())
+    }
+}
+
 /// Mouse binding specific actions.
 #[derive(ConfigDeserialize, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum MouseAction {
@@ -391,7 +650,9 @@ macro_rules! bindings {
                 mods: _mods,
                 mode: _mode,
                 notmode: _notmode,
+                user_mode: None,
                 action: $action.into(),
+                sequence: Vec::new(),
             });
         )*
 
@@ -404,7 +665,7 @@ macro_rules! trigger {
     (KeyBinding, $key:literal,) => {{ BindingKey::Keycode { key: Key::Character($key.into()), location: KeyLocation::Any } }};
     (KeyBinding, $key:ident, $location:expr) => {{ BindingKey::Keycode { key: Key::Named(NamedKey::$key), location: $location } }};
     (KeyBinding, $key:ident,) => {{ BindingKey::Keycode { key: Key::Named(NamedKey::$key), location: KeyLocation::Any } }};
-    (MouseBinding, $base:ident::$button:ident,) => {{ $base::$button }};
+    (MouseBinding, $base:ident::$button:ident,) => {{ MouseTrigger::Button($base::$button) }};
 }
 
 pub fn default_mouse_bindings() -> Vec<MouseBinding> {
@@ -449,6 +710,9 @@ pub fn default_key_bindings() -> Vec<KeyBinding> {
         Backspace, ModifiersState::ALT,     ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::REPORT_ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_ESC_CODES; Action::Esc("\x1b\x7f".into());
         Backspace, ModifiersState::SHIFT,   ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::REPORT_ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_ESC_CODES; Action::Esc("\x7f".into());
         Enter => KeyLocation::Numpad, ~BindingMode::VI, ~BindingMode::SEARCH, ~BindingMode::REPORT_ALL_KEYS_AS_ESC, ~BindingMode::DISAMBIGUATE_ESC_CODES; Action::Esc("\n".into());
+        // Exited shells held open.
+        Enter,  +BindingMode::EXITED, ~BindingMode::SEARCH; Action::RespawnShell;
+        Escape, +BindingMode::EXITED, ~BindingMode::SEARCH; Action::Quit;
         // Vi mode.
         Space, ModifiersState::SHIFT | ModifiersState::CONTROL, ~BindingMode::SEARCH; Action::ToggleViMode;
         Space, ModifiersState::SHIFT | ModifiersState::CONTROL, +BindingMode::VI, ~BindingMode::SEARCH; Action::ScrollToBottom;
@@ -464,8 +728,7 @@ pub fn default_key_bindings() -> Vec<KeyBinding> {
         "f",      ModifiersState::CONTROL,  +BindingMode::VI, ~BindingMode::SEARCH; Action::ScrollPageDown;
         "u",      ModifiersState::CONTROL,  +BindingMode::VI, ~BindingMode::SEARCH; Action::ScrollHalfPageUp;
         "d",      ModifiersState::CONTROL,  +BindingMode::VI, ~BindingMode::SEARCH; Action::ScrollHalfPageDown;
-        "y",                                +BindingMode::VI, ~BindingMode::SEARCH; Action::Copy;
-        "y",                                +BindingMode::VI, ~BindingMode::SEARCH; Action::ClearSelection;
+        "y",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::Yank;
         "/",                                +BindingMode::VI, ~BindingMode::SEARCH; Action::SearchForward;
         "?",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; Action::SearchBackward;
         "y",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::ToggleNormalSelection;
@@ -486,6 +749,13 @@ pub fn default_key_bindings() -> Vec<KeyBinding> {
         "t",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchBackwardShort;
         ";",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchNext;
         ",",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::InlineSearchPrevious;
+        "m",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::SetMark;
+        "`",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::JumpToMark;
+        "'",                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::JumpToMarkLine;
+        "o",      ModifiersState::CONTROL,  +BindingMode::VI, ~BindingMode::SEARCH; ViAction::JumpBackward;
+        "i",      ModifiersState::CONTROL,  +BindingMode::VI, ~BindingMode::SEARCH; ViAction::JumpForward;
+        Tab,                                +BindingMode::VI, ~BindingMode::SEARCH; ViAction::JumpForward;
+        ":",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::GotoLine;
         "*",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::SemanticSearchForward;
         "#",      ModifiersState::SHIFT,    +BindingMode::VI, ~BindingMode::SEARCH; ViAction::SemanticSearchBackward;
         "k",                                +BindingMode::VI, ~BindingMode::SEARCH; ViMotion::Up;
@@ -540,6 +810,7 @@ fn common_keybindings() -> Vec<KeyBinding> {
         "v",    ModifiersState::CONTROL | ModifiersState::SHIFT, +BindingMode::VI, +BindingMode::SEARCH; Action::Paste;
         "f",    ModifiersState::CONTROL | ModifiersState::SHIFT, ~BindingMode::SEARCH;                   Action::SearchForward;
         "b",    ModifiersState::CONTROL | ModifiersState::SHIFT, ~BindingMode::SEARCH;                   Action::SearchBackward;
+        "p",    ModifiersState::CONTROL | ModifiersState::SHIFT, ~BindingMode::SEARCH;                   Action::CommandPalette;
         Insert, ModifiersState::SHIFT,                           ~BindingMode::VI;                       Action::PasteSelection;
         "c",    ModifiersState::CONTROL | ModifiersState::SHIFT;                                         Action::Copy;
         "c",    ModifiersState::CONTROL | ModifiersState::SHIFT, +BindingMode::VI, ~BindingMode::SEARCH; Action::ClearSelection;
@@ -606,6 +877,7 @@ pub fn platform_key_bindings() -> Vec<KeyBinding> {
         "w",    ModifiersState::SUPER;                                         Action::Quit;
         "f",    ModifiersState::SUPER, ~BindingMode::SEARCH;                   Action::SearchForward;
         "b",    ModifiersState::SUPER, ~BindingMode::SEARCH;                   Action::SearchBackward;
+        "p",    ModifiersState::SUPER | ModifiersState::SHIFT, ~BindingMode::SEARCH; Action::CommandPalette;
         "+" => KeyLocation::Numpad, ModifiersState::SUPER;                     Action::IncreaseFontSize;
         "-" => KeyLocation::Numpad, ModifiersState::SUPER;                     Action::DecreaseFontSize;
     )
@@ -740,10 +1012,11 @@ impl<'a> Deserialize<'a> for BindingKey {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ModeWrapper {
     pub mode: BindingMode,
     pub not_mode: BindingMode,
+    pub user_mode: Option<String>,
 }
 
 bitflags! {
@@ -757,17 +1030,19 @@ bitflags! {
         const SEARCH                 = 0b0001_0000;
         const DISAMBIGUATE_ESC_CODES = 0b0010_0000;
         const REPORT_ALL_KEYS_AS_ESC = 0b0100_0000;
+        const EXITED                 = 0b1000_0000;
     }
 }
 
 impl BindingMode {
-    pub fn new(mode: &TermMode, search: bool) -> BindingMode {
+    pub fn new(mode: &TermMode, search: bool, exited: bool) -> BindingMode {
         let mut binding_mode = BindingMode::empty();
         binding_mode.set(BindingMode::APP_CURSOR, mode.contains(TermMode::APP_CURSOR));
         binding_mode.set(BindingMode::APP_KEYPAD, mode.contains(TermMode::APP_KEYPAD));
         binding_mode.set(BindingMode::ALT_SCREEN, mode.contains(TermMode::ALT_SCREEN));
         binding_mode.set(BindingMode::VI, mode.contains(TermMode::VI));
         binding_mode.set(BindingMode::SEARCH, search);
+        binding_mode.set(BindingMode::EXITED, exited);
         binding_mode.set(
             BindingMode::DISAMBIGUATE_ESC_CODES,
             mode.contains(TermMode::DISAMBIGUATE_ESC_CODES),
@@ -782,7 +1057,7 @@ impl BindingMode {
 
 impl Default for ModeWrapper {
     fn default() -> Self {
-        Self { mode: BindingMode::empty(), not_mode: BindingMode::empty() }
+        Self { mode: BindingMode::empty(), not_mode: BindingMode::empty(), user_mode: None }
     }
 }
 
@@ -798,7 +1073,8 @@ impl<'a> Deserialize<'a> for ModeWrapper {
 
             fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 f.write_str(
-                    "a combination of AppCursor | AppKeypad | Alt | Vi, possibly with negation (~)",
+                    "a combination of AppCursor | AppKeypad | Alt | Vi, possibly with negation (~), \
+                     and a user-defined mode like User:name",
                 )
             }
 
@@ -806,8 +1082,7 @@ impl<'a> Deserialize<'a> for ModeWrapper {
             where
                 E: de::Error,
             {
-                let mut res =
-                    ModeWrapper { mode: BindingMode::empty(), not_mode: BindingMode::empty() };
+                let mut res = ModeWrapper::default();
 
                 for modifier in value.split('|') {
                     match modifier.trim().to_lowercase().as_str() {
@@ -821,7 +1096,20 @@ impl<'a> Deserialize<'a> for ModeWrapper {
                         "~vi" => res.not_mode |= BindingMode::VI,
                         "search" => res.mode |= BindingMode::SEARCH,
                         "~search" => res.not_mode |= BindingMode::SEARCH,
-                        _ => return Err(E::invalid_value(Unexpected::Str(modifier), &self)),
+                        "exited" => res.mode |= BindingMode::EXITED,
+                        "~exited" => res.not_mode |= BindingMode::EXITED,
+                        modifier => match modifier.strip_prefix("user:") {
+                            Some(name)
+                                if res.user_mode.is_none()
+                                    && !name.is_empty()
+                                    && name
+                                        .chars()
+                                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-') =>
+                            {
+                                res.user_mode = Some(name.into());
+                            },
+                            _ => return Err(E::invalid_value(Unexpected::Str(modifier), &self)),
+                        },
                     }
                 }
 
@@ -832,64 +1120,113 @@ impl<'a> Deserialize<'a> for ModeWrapper {
     }
 }
 
-struct MouseButtonWrapper(MouseButton);
+/// Mouse input which can trigger a binding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MouseTrigger {
+    /// Press of a mouse button.
+    Button(MouseButton),
 
-impl MouseButtonWrapper {
-    fn into_inner(self) -> MouseButton {
-        self.0
-    }
+    /// Second press of a mouse button in quick succession.
+    DoubleClick(MouseButton),
+
+    /// Third press of a mouse button in quick succession.
+    TripleClick(MouseButton),
+
+    /// Mouse movement while a button is held down.
+    Drag(MouseButton),
+
+    /// Mouse wheel scrolled up by one line.
+    WheelUp,
+
+    /// Mouse wheel scrolled down by one line.
+    WheelDown,
 }
 
-impl<'a> Deserialize<'a> for MouseButtonWrapper {
+impl<'a> Deserialize<'a> for MouseTrigger {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'a>,
     {
-        struct MouseButtonVisitor;
+        struct MouseTriggerVisitor;
 
-        impl Visitor<'_> for MouseButtonVisitor {
-            type Value = MouseButtonWrapper;
+        impl Visitor<'_> for MouseTriggerVisitor {
+            type Value = MouseTrigger;
 
             fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                f.write_str("Left, Right, Middle, Back, Forward, or a number from 0 to 65536")
+                f.write_str(
+                    "Left, Right, Middle, Back, Forward, a number from 0 to 65536, WheelUp, \
+                     WheelDown, a button prefixed with Double or Triple, or Left, Right or \
+                     Middle prefixed with Drag",
+                )
             }
 
-            fn visit_i64<E>(self, value: i64) -> Result<MouseButtonWrapper, E>
+            fn visit_i64<E>(self, value: i64) -> Result<MouseTrigger, E>
             where
                 E: de::Error,
             {
                 match value {
-                    0..=65536 => Ok(MouseButtonWrapper(MouseButton::Other(value as u16))),
+                    0..=65536 => Ok(MouseTrigger::Button(MouseButton::Other(value as u16))),
                     _ => Err(E::invalid_value(Unexpected::Signed(value), &self)),
                 }
             }
 
-            fn visit_u64<E>(self, value: u64) -> Result<MouseButtonWrapper, E>
+            fn visit_u64<E>(self, value: u64) -> Result<MouseTrigger, E>
             where
                 E: de::Error,
             {
                 match value {
-                    0..=65536 => Ok(MouseButtonWrapper(MouseButton::Other(value as u16))),
+                    0..=65536 => Ok(MouseTrigger::Button(MouseButton::Other(value as u16))),
                     _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
                 }
             }
 
-            fn visit_str<E>(self, value: &str) -> Result<MouseButtonWrapper, E>
+            fn visit_str<E>(self, value: &str) -> Result<MouseTrigger, E>
             where
                 E: de::Error,
             {
-                match value {
-                    "Left" => Ok(MouseButtonWrapper(MouseButton::Left)),
-                    "Right" => Ok(MouseButtonWrapper(MouseButton::Right)),
-                    "Middle" => Ok(MouseButtonWrapper(MouseButton::Middle)),
-                    "Back" => Ok(MouseButtonWrapper(MouseButton::Back)),
-                    "Forward" => Ok(MouseButtonWrapper(MouseButton::Forward)),
-                    _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
-                }
+                let trigger = match value {
+                    "WheelUp" => Some(MouseTrigger::WheelUp),
+                    "WheelDown" => Some(MouseTrigger::WheelDown),
+                    _ => {
+                        if let Some(button) = value.strip_prefix("Double") {
+                            parse_mouse_button(button).map(MouseTrigger::DoubleClick)
+                        } else if let Some(button) = value.strip_prefix("Triple") {
+                            parse_mouse_button(button).map(MouseTrigger::TripleClick)
+                        } else if let Some(button) = value.strip_prefix("Drag") {
+                            // Only these buttons have their pressed state tracked.
+                            parse_mouse_button(button)
+                                .filter(|button| {
+                                    matches!(
+                                        button,
+                                        MouseButton::Left
+                                            | MouseButton::Right
+                                            | MouseButton::Middle
+                                    )
+                                })
+                                .map(MouseTrigger::Drag)
+                        } else {
+                            parse_mouse_button(value).map(MouseTrigger::Button)
+                        }
+                    },
+                };
+
+                trigger.ok_or_else(|| E::invalid_value(Unexpected::Str(value), &self))
             }
         }
 
-        deserializer.deserialize_any(MouseButtonVisitor)
+        deserializer.deserialize_any(MouseTriggerVisitor)
+    }
+}
+
+/// Parse the name of a mouse button.
+fn parse_mouse_button(button: &str) -> Option<MouseButton> {
+    match button {
+        "Left" => Some(MouseButton::Left),
+        "Right" => Some(MouseButton::Right),
+        "Middle" => Some(MouseButton::Middle),
+        "Back" => Some(MouseButton::Back),
+        "Forward" => Some(MouseButton::Forward),
+        _ => None,
     }
 }
 
@@ -898,11 +1235,13 @@ impl<'a> Deserialize<'a> for MouseButtonWrapper {
 #[derive(PartialEq, Eq)]
 struct RawBinding {
     key: Option<BindingKey>,
-    mouse: Option<MouseButton>,
+    mouse: Option<MouseTrigger>,
     mods: ModifiersState,
     mode: BindingMode,
     notmode: BindingMode,
+    user_mode: Option<String>,
     action: Action,
+    sequence: Vec<(BindingKey, ModifiersState)>,
 }
 
 impl RawBinding {
@@ -914,6 +1253,8 @@ impl RawBinding {
                 action: self.action,
                 mode: self.mode,
                 notmode: self.notmode,
+                user_mode: self.user_mode,
+                sequence: Vec::new(),
             })
         } else {
             Err(Box::new(self))
@@ -928,6 +1269,8 @@ impl RawBinding {
                 action: self.action,
                 mode: self.mode,
                 notmode: self.notmode,
+                user_mode: self.user_mode,
+                sequence: self.sequence,
             })
         } else {
             Err(Box::new(self))
@@ -1002,10 +1345,12 @@ impl<'a> Deserialize<'a> for RawBinding {
                 let mut mods: Option<ModifiersState> = None;
                 let mut key: Option<BindingKey> = None;
                 let mut chars: Option<String> = None;
+                let mut sequence: Option<Vec<(BindingKey, ModifiersState)>> = None;
                 let mut action: Option<Action> = None;
                 let mut mode: Option<BindingMode> = None;
                 let mut not_mode: Option<BindingMode> = None;
-                let mut mouse: Option<MouseButton> = None;
+                let mut user_mode: Option<String> = None;
+                let mut mouse: Option<MouseTrigger> = None;
                 let mut command: Option<Program> = None;
 
                 use de::Error;
@@ -1053,6 +1398,7 @@ impl<'a> Deserialize<'a> for RawBinding {
                             let mode_deserializer = map.next_value::<ModeWrapper>()?;
                             mode = Some(mode_deserializer.mode);
                             not_mode = Some(mode_deserializer.not_mode);
+                            user_mode = mode_deserializer.user_mode;
                         },
                         Field::Action => {
                             if action.is_some() {
@@ -1060,47 +1406,37 @@ impl<'a> Deserialize<'a> for RawBinding {
                             }
 
                             let value = map.next_value::<SerdeValue>()?;
-
-                            action = if let Ok(vi_action) = ViAction::deserialize(value.clone()) {
-                                Some(vi_action.into())
-                            } else if let Ok(vi_motion) = SerdeViMotion::deserialize(value.clone())
-                            {
-                                Some(vi_motion.0.into())
-                            } else if let Ok(search_action) =
-                                SearchAction::deserialize(value.clone())
-                            {
-                                Some(search_action.into())
-                            } else if let Ok(mouse_action) = MouseAction::deserialize(value.clone())
-                            {
-                                Some(mouse_action.into())
-                            } else {
-                                match Action::deserialize(value.clone()).map_err(V::Error::custom) {
-                                    Ok(action) => Some(action),
-                                    Err(err) => {
-                                        let value = match value {
-                                            SerdeValue::String(string) => string,
-                                            _ => return Err(err),
-                                        };
-                                        return Err(V::Error::custom(format!(
-                                            "unknown keyboard action `{value}`"
-                                        )));
-                                    },
-                                }
-                            };
+                            action = Some(parse_action(value).map_err(V::Error::custom)?);
                         },
                         Field::Chars => {
-                            if chars.is_some() {
+                            if chars.is_some() || sequence.is_some() {
                                 return Err(<V::Error as Error>::duplicate_field("chars"));
                             }
 
-                            chars = Some(map.next_value()?);
+                            match map.next_value::<SerdeValue>()? {
+                                SerdeValue::Array(keys) if !keys.is_empty() => {
+                                    let keys = keys
+                                        .iter()
+                                        .map(|key| match key.as_str() {
+                                            Some(key) => parse_sequence_key(key),
+                                            None => Err(format!("invalid key `{key}`")),
+                                        })
+                                        .collect::<Result<_, _>>()
+                                        .map_err(V::Error::custom)?;
+                                    sequence = Some(keys);
+                                },
+                                value => {
+                                    chars =
+                                        Some(String::deserialize(value).map_err(V::Error::custom)?)
+                                },
+                            }
                         },
                         Field::Mouse => {
                             if mouse.is_some() {
                                 return Err(<V::Error as Error>::duplicate_field("mouse"));
                             }
 
-                            mouse = Some(map.next_value::<MouseButtonWrapper>()?.into_inner());
+                            mouse = Some(map.next_value::<MouseTrigger>()?);
                         },
                         Field::Command => {
                             if command.is_some() {
@@ -1114,7 +1450,21 @@ impl<'a> Deserialize<'a> for RawBinding {
 
                 let mode = mode.unwrap_or_else(BindingMode::empty);
                 let not_mode = not_mode.unwrap_or_else(BindingMode::empty);
-                let mods = mods.unwrap_or_default();
+                let mut mods = mods.unwrap_or_default();
+
+                // The first key of a sequence is used as the binding's trigger.
+                let mut sequence = sequence.unwrap_or_default();
+                if !sequence.is_empty() {
+                    if key.is_some() || mouse.is_some() || mods != ModifiersState::empty() {
+                        return Err(V::Error::custom(
+                            "key sequences can't be combined with key, mods or mouse",
+                        ));
+                    }
+
+                    let (first_key, first_mods) = sequence.remove(0);
+                    key = Some(first_key);
+                    mods = first_mods;
+                }
 
                 let action = match (action, chars, command) {
                     (Some(action @ Action::ViMotion(_)), None, None)
@@ -1142,7 +1492,16 @@ impl<'a> Deserialize<'a> for RawBinding {
                     return Err(V::Error::custom("bindings require mouse button or key"));
                 }
 
-                Ok(RawBinding { mode, notmode: not_mode, action, key, mouse, mods })
+                Ok(RawBinding {
+                    mode,
+                    notmode: not_mode,
+                    user_mode,
+                    action,
+                    key,
+                    mouse,
+                    mods,
+                    sequence,
+                })
             }
         }
 
@@ -1238,6 +1597,78 @@ impl<'a> de::Deserialize<'a> for ModsWrapper {
     }
 }
 
+/// Parse a binding's action.
+///
+/// Besides the action names, this accepts tables like `{ chars = "ls\n" }` and lists of
+/// actions which are executed in order.
+fn parse_action(value: SerdeValue) -> Result<Action, String> {
+    let table = match value {
+        SerdeValue::Array(values) => {
+            let actions = values.into_iter().map(parse_action).collect::<Result<_, _>>()?;
+            return Ok(Action::Sequence(actions));
+        },
+        SerdeValue::Table(table) => table,
+        value => {
+            return if let Ok(vi_action) = ViAction::deserialize(value.clone()) {
+                Ok(vi_action.into())
+            } else if let Ok(vi_motion) = SerdeViMotion::deserialize(value.clone()) {
+                Ok(vi_motion.0.into())
+            } else if let Ok(search_action) = SearchAction::deserialize(value.clone()) {
+                Ok(search_action.into())
+            } else if let Ok(mouse_action) = MouseAction::deserialize(value.clone()) {
+                Ok(mouse_action.into())
+            } else {
+                match Action::deserialize(value.clone()) {
+                    Ok(action) => Ok(action),
+                    Err(_) if value.is_str() => {
+                        Err(format!("unknown keyboard action `{}`", value.as_str().unwrap()))
+                    },
+                    Err(err) => Err(err.to_string()),
+                }
+            };
+        },
+    };
+
+    let mut entries = table.into_iter();
+    let action = match (entries.next(), entries.next()) {
+        (Some((name, value)), None) => match name.as_str() {
+            "chars" => String::deserialize(value).map(Action::Esc),
+            "command" => Program::deserialize(value).map(Action::Command),
+            "Copy" => CopyOptions::deserialize(value).map(Action::CopyWith),
+            "Paste" => PasteOptions::deserialize(value).map(Action::PasteWith),
+            "EnterMode" => {
+                String::deserialize(value).map(|mode| Action::EnterMode(mode.to_lowercase()))
+            },
+            "Signal" => Signal::deserialize(value).map(Action::Signal),
+            _ => return Err(format!("unknown keyboard action `{name}`")),
+        },
+        _ => return Err("action tables must have exactly one entry".into()),
+    };
+
+    action.map_err(|err| err.to_string())
+}
+
+/// Parse a key of a binding sequence, like `Control+A`.
+fn parse_sequence_key(value: &str) -> Result<(BindingKey, ModifiersState), String> {
+    let (modifiers, key) = value.rsplit_once('+').unwrap_or(("", value));
+
+    let mut mods = ModifiersState::empty();
+    for modifier in modifiers.split('+').filter(|modifier| !modifier.is_empty()) {
+        match modifier.trim().to_lowercase().as_str() {
+            "command" | "super" => mods.insert(ModifiersState::SUPER),
+            "shift" => mods.insert(ModifiersState::SHIFT),
+            "alt" | "option" => mods.insert(ModifiersState::ALT),
+            "control" | "ctrl" => mods.insert(ModifiersState::CONTROL),
+            _ => return Err(format!("invalid modifier `{modifier}` in key `{value}`")),
+        }
+    }
+
+    let key = BindingKey::deserialize(SerdeValue::String(key.trim().into()))
+        .map_err(|err| format!("invalid key `{value}`: {err}"))?;
+
+    Ok((key, mods))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1253,7 +1684,9 @@ mod tests {
                 action: Action::None,
                 mode: BindingMode::empty(),
                 notmode: BindingMode::empty(),
+                user_mode: None,
                 trigger: Default::default(),
+                sequence: Vec::new(),
             }
         }
     }
@@ -1443,4 +1876,175 @@ mod tests {
         assert!(!binding.is_triggered_by(BindingMode::ALT_SCREEN, mods, &t));
         assert!(!binding.is_triggered_by(BindingMode::ALT_SCREEN | BindingMode::VI, mods, &t));
     }
+
+    #[test]
+    fn deserialize_key_sequence() {
+        let value: toml::Value =
+            toml::from_str("chars = [\"Ctrl+A\", \"Shift+Alt+c\"]\naction = \"Copy\"").unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+
+        let key = |key: &str| BindingKey::Keycode {
+            key: Key::Character(key.into()),
+            location: KeyLocation::Any,
+        };
+        assert_eq!(binding.trigger, key("a"));
+        assert_eq!(binding.mods, ModifiersState::CONTROL);
+        assert_eq!(binding.sequence, [(key("c"), ModifiersState::SHIFT | ModifiersState::ALT)]);
+        assert_eq!(binding.action, Action::Copy);
+
+        let value: toml::Value = toml::from_str(r#"chars = ["Ctrl+A"]"#).unwrap();
+        assert!(KeyBinding::deserialize(value).is_err());
+    }
+
+    #[test]
+    fn exited_bindings() {
+        let value: toml::Value =
+            toml::from_str("key = \"Q\"\nmode = \"Exited\"\naction = \"Quit\"").unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        assert_eq!(binding.mode, BindingMode::EXITED);
+
+        let exited = BindingMode::new(&TermMode::default(), false, true);
+        let enter =
+            BindingKey::Keycode { key: Key::Named(NamedKey::Enter), location: KeyLocation::Any };
+        let actions: Vec<_> = default_key_bindings()
+            .into_iter()
+            .filter(|binding| binding.mode.contains(BindingMode::EXITED))
+            .filter(|binding| binding.is_triggered_by(exited, ModifiersState::empty(), &enter))
+            .map(|binding| binding.action)
+            .collect();
+        assert_eq!(actions, [Action::RespawnShell]);
+    }
+
+    #[test]
+    fn deserialize_user_mode() {
+        let value: toml::Value =
+            toml::from_str("key = \"R\"\nmods = \"Control\"\naction = { EnterMode = \"Resize\" }")
+                .unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        assert_eq!(binding.action, Action::EnterMode("resize".into()));
+        assert!(binding.in_user_mode(None));
+
+        let value: toml::Value =
+            toml::from_str("key = \"Escape\"\nmode = \"~Vi|User:resize\"\naction = \"ExitMode\"")
+                .unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        assert_eq!(binding.action, Action::ExitMode);
+        assert_eq!(binding.notmode, BindingMode::VI);
+        assert!(binding.in_user_mode(Some("resize")));
+        assert!(!binding.in_user_mode(None));
+
+        let value: toml::Value = toml::from_str("mode = \"User:resize|User:copy\"").unwrap();
+        assert!(ModeWrapper::deserialize(value["mode"].clone()).is_err());
+
+        // Misspelled built-in modes aren't user modes.
+        for mode in ["Vim", "AppCursr", "resize", "User:"] {
+            let value = toml::Value::String(mode.into());
+            assert!(ModeWrapper::deserialize(value).is_err());
+        }
+    }
+
+    #[test]
+    fn deserialize_action_sequence() {
+        let value: toml::Value = toml::from_str(
+            "key = \"C\"\naction = [\"Copy\", \"ClearSelection\", { chars = \"ls\" }, \"ScrollToBottom\"]",
+        )
+        .unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        assert_eq!(
+            binding.action,
+            Action::Sequence(vec![
+                Action::Copy,
+                Action::ClearSelection,
+                Action::Esc("ls".into()),
+                Action::ScrollToBottom,
+            ])
+        );
+
+        let value: toml::Value = toml::from_str(
+            "key = \"C\"\naction = [\"Copy\", { chars = \"ls\", command = \"ls\" }]",
+        )
+        .unwrap();
+        assert!(KeyBinding::deserialize(value).is_err());
+    }
+
+    #[test]
+    fn deserialize_mouse_triggers() {
+        let trigger = |mouse: &str| {
+            let value: toml::Value =
+                toml::from_str(&format!("mouse = \"{mouse}\"\naction = \"Copy\"")).unwrap();
+            MouseBinding::deserialize(value).map(|binding| binding.trigger)
+        };
+
+        assert_eq!(trigger("Left").unwrap(), MouseTrigger::Button(MouseButton::Left));
+        assert_eq!(trigger("WheelUp").unwrap(), MouseTrigger::WheelUp);
+        assert_eq!(trigger("WheelDown").unwrap(), MouseTrigger::WheelDown);
+        assert_eq!(trigger("DoubleLeft").unwrap(), MouseTrigger::DoubleClick(MouseButton::Left));
+        assert_eq!(trigger("TripleRight").unwrap(), MouseTrigger::TripleClick(MouseButton::Right));
+        assert_eq!(trigger("DragMiddle").unwrap(), MouseTrigger::Drag(MouseButton::Middle));
+        assert!(trigger("DoubleWheelUp").is_err());
+        assert!(trigger("Drag").is_err());
+        assert!(trigger("DragBack").is_err());
+        assert!(trigger("DragForward").is_err());
+    }
+
+    #[test]
+    fn deserialize_copy_options() {
+        let value: toml::Value =
+            toml::from_str("key = \"C\"\naction = { Copy = { trim = true, dedent = true } }")
+                .unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        let options = CopyOptions { trim: true, dedent: true, single_line: false };
+        assert_eq!(binding.action, Action::CopyWith(options));
+
+        let value: toml::Value =
+            toml::from_str("key = \"C\"\naction = { Copy = { unknown = true } }").unwrap();
+        assert!(KeyBinding::deserialize(value).is_err());
+    }
+
+    #[test]
+    fn deserialize_signals() {
+        let value: toml::Value =
+            toml::from_str("key = \"C\"\naction = { Signal = \"SIGINT\" }").unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        assert_eq!(binding.action, Action::Signal(Signal::Int));
+
+        assert_eq!("term".parse(), Ok(Signal::Term));
+        assert_eq!(Signal::Tstp.to_string(), "SIGTSTP");
+        assert!("SIGWHAT".parse::<Signal>().is_err());
+    }
+
+    #[test]
+    fn copy_transformations() {
+        let text = "    fn main() {  \n        run();\n\n    }  \n";
+
+        let trim = CopyOptions { trim: true, ..Default::default() };
+        assert_eq!(trim.apply(text), "    fn main() {\n        run();\n\n    }\n");
+
+        let dedent = CopyOptions { trim: true, dedent: true, ..Default::default() };
+        assert_eq!(dedent.apply(text), "fn main() {\n    run();\n\n}\n");
+
+        let single_line = CopyOptions { single_line: true, ..Default::default() };
+        assert_eq!(single_line.apply(text), "fn main() { run(); }");
+    }
+
+    #[test]
+    fn paste_transformations() {
+        let text = "echo 'a'\r\n  b\n";
+
+        let strip_newlines = PasteOptions { strip_newlines: true, ..Default::default() };
+        assert_eq!(strip_newlines.apply(text), "echo 'a'  b");
+
+        let single_line = PasteOptions { single_line: true, ..Default::default() };
+        assert_eq!(single_line.apply(text), "echo 'a' b");
+
+        let shell_quote =
+            PasteOptions { single_line: true, shell_quote: true, ..Default::default() };
+        assert_eq!(shell_quote.apply(text), "'echo '\\''a'\\'' b'");
+
+        let value: toml::Value =
+            toml::from_str("key = \"V\"\naction = { Paste = { shell_quote = true } }").unwrap();
+        let binding = KeyBinding::deserialize(value).unwrap();
+        let options = PasteOptions { shell_quote: true, ..Default::default() };
+        assert_eq!(binding.action, Action::PasteWith(options));
+    }
 }
@@ -31,7 +31,8 @@ use crate::cli::Options;
 #[cfg(test)]
 pub use crate::config::bindings::Binding;
 pub use crate::config::bindings::{
-    Action, BindingKey, BindingMode, KeyBinding, MouseAction, SearchAction, ViAction,
+    Action, BindingKey, BindingMode, CopyOptions, KeyBinding, MouseAction, MouseBinding,
+    MouseTrigger, SearchAction, Signal, ViAction,
 };
 pub use crate::config::ui_config::UiConfig;
 use crate::logging::LOG_TARGET_CONFIG;
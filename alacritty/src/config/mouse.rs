@@ -8,6 +8,9 @@ use crate::config::ui_config;
 #[derive(ConfigDeserialize, Serialize, Default, Clone, Debug, PartialEq, Eq)]
 pub struct Mouse {
     pub hide_when_typing: bool,
+
+    /// Move the shell's cursor with Alt+click on the primary screen.
+    pub alt_click_moves_cursor: bool,
     #[serde(skip_serializing)]
     pub bindings: MouseBindings,
 }
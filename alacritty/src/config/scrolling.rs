@@ -11,12 +11,27 @@ pub const MAX_SCROLLBACK_LINES: u32 = 100_000;
 pub struct Scrolling {
     pub multiplier: u8,
 
+    /// Line numbers shown in vi mode.
+    pub line_numbers: LineNumbers,
+
     history: ScrollingHistory,
+
+    /// Memory limit for lines rotated out of the history in megabytes.
+    compressed_history: u32,
+
+    /// Log file for lines rotated out of the history.
+    pub spill: Spill,
 }
 
 impl Default for Scrolling {
     fn default() -> Self {
-        Self { multiplier: 3, history: Default::default() }
+        Self {
+            multiplier: 3,
+            line_numbers: Default::default(),
+            history: Default::default(),
+            compressed_history: Default::default(),
+            spill: Default::default(),
+        }
     }
 }
 
@@ -24,6 +39,48 @@ impl Scrolling {
     pub fn history(self) -> u32 {
         self.history.0
     }
+
+    /// Memory limit for the compressed history in bytes.
+    pub fn compressed_history(self) -> usize {
+        (self.compressed_history as usize).saturating_mul(1024 * 1024)
+    }
+}
+
+/// Log file for lines rotated out of the history.
+#[derive(ConfigDeserialize, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Spill {
+    /// Append lines rotated out of the history to a log file.
+    pub enabled: bool,
+
+    /// Size in megabytes before the log file is rotated.
+    max_size: u32,
+
+    /// Number of rotated log files which are kept.
+    pub files: u8,
+}
+
+impl Default for Spill {
+    fn default() -> Self {
+        Self { enabled: false, max_size: 10, files: 5 }
+    }
+}
+
+impl Spill {
+    /// Size in bytes before the log file is rotated.
+    pub fn max_size(self) -> u64 {
+        u64::from(self.max_size.max(1)) * 1024 * 1024
+    }
+}
+
+/// Line numbers shown in a gutter left of the grid in vi mode.
+#[derive(ConfigDeserialize, Serialize, Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LineNumbers {
+    #[default]
+    None,
+    /// Number lines from the top of the scrollback history.
+    Absolute,
+    /// Number lines by their distance to the vi mode cursor.
+    Relative,
 }
 
 #[derive(SerdeReplace, Serialize, Copy, Clone, Debug, PartialEq, Eq)]
@@ -1,12 +1,24 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use alacritty_config_derive::ConfigDeserialize;
+use alacritty_terminal::selection::SelectionType;
 use alacritty_terminal::term::SEMANTIC_ESCAPE_CHARS;
 
+use crate::config::ui_config::LazyRegex;
+
 #[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub struct Selection {
     pub semantic_escape_chars: String,
     pub save_to_clipboard: bool,
+
+    /// Selection started by a double click.
+    pub double_click: ClickSelection,
+
+    /// Selection started by a triple click.
+    pub triple_click: ClickSelection,
+
+    /// Patterns preferred over semantic escape characters for semantic selection.
+    pub smart_rules: Vec<SmartRule>,
 }
 
 impl Default for Selection {
@@ -14,6 +26,37 @@ impl Default for Selection {
         Self {
             semantic_escape_chars: SEMANTIC_ESCAPE_CHARS.to_owned(),
             save_to_clipboard: Default::default(),
+            double_click: ClickSelection::Semantic,
+            triple_click: ClickSelection::Lines,
+            smart_rules: Default::default(),
+        }
+    }
+}
+
+/// Text selected by multiple clicks.
+#[derive(ConfigDeserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClickSelection {
+    /// Word bounded by the semantic escape characters.
+    Semantic,
+    /// Logical line, following line wraps.
+    Lines,
+    /// Visual row, ignoring line wraps.
+    Rows,
+}
+
+impl ClickSelection {
+    pub fn selection_type(self) -> SelectionType {
+        match self {
+            Self::Semantic => SelectionType::Semantic,
+            Self::Lines => SelectionType::Lines,
+            Self::Rows => SelectionType::Rows,
         }
     }
 }
+
+/// Pattern selected as a whole when clicking anywhere inside of it.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct SmartRule {
+    pub regex: LazyRegex,
+}
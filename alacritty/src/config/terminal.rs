@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Deserializer, Serialize, de};
 use toml::Value;
 
 use alacritty_config_derive::{ConfigDeserialize, SerdeReplace};
 use alacritty_terminal::term::Osc52;
+use alacritty_terminal::term::window_ops::WindowOps;
 
 use crate::config::ui_config::{Program, StringVisitor};
 
@@ -12,6 +15,109 @@ pub struct Terminal {
     pub osc52: SerdeOsc52,
     /// Path to a shell program to run on startup.
     pub shell: Option<Program>,
+    /// Confirmation of potentially dangerous pastes.
+    pub paste: Paste,
+    /// Confirmation of closing windows with running programs.
+    pub close: Close,
+    /// Behavior of the window once the shell exits.
+    pub on_exit: OnExit,
+    /// Alerts for output activity and silence of unfocused windows.
+    pub monitor: Monitor,
+    /// Xterm window operations applications are allowed to use.
+    pub window_ops: SerdeWindowOps,
+}
+
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Paste {
+    /// Confirm pastes with line breaks outside of bracketed paste mode or control characters.
+    pub confirm: bool,
+    /// Confirm pastes exceeding this number of bytes, `0` disables the limit.
+    pub max_size: usize,
+    /// Foreground programs receiving pastes without confirmation.
+    pub trusted_programs: Vec<String>,
+}
+
+impl Default for Paste {
+    fn default() -> Self {
+        Self { confirm: true, max_size: 0, trusted_programs: Default::default() }
+    }
+}
+
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Close {
+    /// Confirm closing the window while a program other than the shell is in the foreground.
+    pub confirm: bool,
+    /// Foreground programs which can be closed without confirmation.
+    pub ignored_programs: Vec<String>,
+}
+
+impl Default for Close {
+    fn default() -> Self {
+        Self { confirm: true, ignored_programs: Default::default() }
+    }
+}
+
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct Monitor {
+    /// Alert when an unfocused window produces output.
+    pub activity: bool,
+    /// Alert when an unfocused window produces no output for this many seconds, `0` disables it.
+    silence: u32,
+    /// Set the urgency hint of alerted windows.
+    pub urgent: bool,
+    /// Ring the bell of alerted windows.
+    pub bell: bool,
+    /// Command executed on alerts, with the kind of alert and the window title appended.
+    pub command: Option<Program>,
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self {
+            urgent: true,
+            activity: Default::default(),
+            silence: Default::default(),
+            bell: Default::default(),
+            command: Default::default(),
+        }
+    }
+}
+
+impl Monitor {
+    /// Duration without output after which silence is reported.
+    pub fn silence(&self) -> Option<Duration> {
+        (self.silence != 0).then(|| Duration::from_secs(self.silence.into()))
+    }
+}
+
+/// Window behavior once the shell exits.
+#[derive(SerdeReplace, Serialize, Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OnExit {
+    /// Close the window.
+    #[default]
+    Close,
+    /// Keep the window open.
+    Hold,
+    /// Keep the window open if the shell exited with a non-zero status.
+    HoldOnError,
+}
+
+impl<'de> Deserialize<'de> for OnExit {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = deserializer.deserialize_str(StringVisitor)?;
+        match value.replace('_', "").as_str() {
+            "close" => Ok(Self::Close),
+            "hold" => Ok(Self::Hold),
+            "holdonerror" => Ok(Self::HoldOnError),
+            _ => Err(de::Error::custom(format!(
+                "unknown variant `{value}`, expected one of `close`, `hold`, `hold_on_error`"
+            ))),
+        }
+    }
 }
 
 #[derive(SerdeReplace, Serialize, Default, Copy, Clone, Debug, PartialEq)]
@@ -26,3 +132,35 @@ impl<'de> Deserialize<'de> for SerdeOsc52 {
         Osc52::deserialize(Value::String(value)).map(SerdeOsc52).map_err(de::Error::custom)
     }
 }
+
+#[derive(SerdeReplace, Serialize, Default, Copy, Clone, Debug, PartialEq)]
+pub struct SerdeWindowOps(pub WindowOps);
+
+impl<'de> Deserialize<'de> for SerdeWindowOps {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        WindowOps::deserialize(deserializer).map(SerdeWindowOps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitor_silence() {
+        assert_eq!(Monitor::default().silence(), None);
+
+        let monitor = Monitor { silence: 30, ..Default::default() };
+        assert_eq!(monitor.silence(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn deserialize_window_ops() {
+        let terminal: Terminal = toml::from_str("window_ops = { resize = true }").unwrap();
+        let window_ops = WindowOps { resize: true, ..Default::default() };
+        assert_eq!(terminal.window_ops.0, window_ops);
+    }
+}
@@ -5,6 +5,7 @@ use std::fmt::{self, Formatter};
 use std::mem;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Duration;
 
 use log::{error, warn};
 use serde::de::{Error as SerdeError, MapAccess, Visitor};
@@ -32,7 +33,7 @@ use crate::config::general::General;
 use crate::config::mouse::Mouse;
 use crate::config::scrolling::Scrolling;
 use crate::config::selection::Selection;
-use crate::config::terminal::Terminal;
+use crate::config::terminal::{OnExit, Terminal};
 use crate::config::window::WindowConfig;
 
 /// Regex used for the default URL hint.
@@ -120,9 +121,12 @@ impl UiConfig {
         TermConfig {
             semantic_escape_chars: self.selection.semantic_escape_chars.clone(),
             scrolling_history: self.scrolling.history() as usize,
+            compressed_history: self.scrolling.compressed_history(),
+            collect_evicted_lines: self.scrolling.spill.enabled,
             vi_mode_cursor_style: self.cursor.vi_mode_style(),
             default_cursor_style: self.cursor.style(),
             osc52: self.terminal.osc52.0,
+            window_ops: self.terminal.window_ops.0,
             kitty_keyboard: true,
         }
     }
@@ -135,7 +139,7 @@ impl UiConfig {
         PtyOptions {
             working_directory,
             shell,
-            drain_on_exit: false,
+            drain_on_exit: self.terminal.on_exit != OnExit::Close,
             env: HashMap::new(),
             #[cfg(target_os = "windows")]
             escape_args: false,
@@ -152,6 +156,12 @@ impl UiConfig {
         &self.keyboard.bindings.0
     }
 
+    /// Time to wait for the next key of a key sequence binding.
+    #[inline]
+    pub fn key_sequence_timeout(&self) -> Duration {
+        Duration::from_millis(self.keyboard.sequence_timeout)
+    }
+
     #[inline]
     pub fn mouse_bindings(&self) -> &[MouseBinding] {
         &self.mouse.bindings.0
@@ -170,11 +180,20 @@ impl UiConfig {
 }
 
 /// Keyboard configuration.
-#[derive(ConfigDeserialize, Serialize, Default, Clone, Debug, PartialEq)]
+#[derive(ConfigDeserialize, Serialize, Clone, Debug, PartialEq)]
 struct Keyboard {
     /// Keybindings.
     #[serde(skip_serializing)]
     bindings: KeyBindings,
+
+    /// Timeout in milliseconds for completing key sequence bindings.
+    sequence_timeout: u64,
+}
+
+impl Default for Keyboard {
+    fn default() -> Self {
+        Self { bindings: Default::default(), sequence_timeout: 1000 }
+    }
 }
 
 #[derive(SerdeReplace, Clone, Debug, PartialEq, Eq)]
@@ -474,7 +493,9 @@ impl HintBinding {
             mods: self.mods.0,
             mode: self.mode.mode,
             notmode: self.mode.not_mode,
+            user_mode: self.mode.user_mode.clone(),
             action: Action::Hint(hint.clone()),
+            sequence: Vec::new(),
         })
     }
 }
@@ -38,6 +38,12 @@ pub struct WindowConfig {
     /// Use dynamic title.
     pub dynamic_title: bool,
 
+    /// Template of the dynamic title.
+    pub title_template: Option<String>,
+
+    /// Maximum width of titles generated from the template.
+    pub title_max_width: usize,
+
     /// Information to identify a particular window.
     #[config(flatten)]
     pub identity: Identity,
@@ -71,6 +77,8 @@ impl Default for WindowConfig {
     fn default() -> Self {
         Self {
             dynamic_title: true,
+            title_max_width: 80,
+            title_template: Default::default(),
             blur: Default::default(),
             embed: Default::default(),
             padding: Default::default(),
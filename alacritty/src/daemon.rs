@@ -93,10 +93,7 @@ pub fn foreground_process_path(
     master_fd: RawFd,
     shell_pid: u32,
 ) -> Result<PathBuf, Box<dyn Error>> {
-    let mut pid = unsafe { libc::tcgetpgrp(master_fd) };
-    if pid < 0 {
-        pid = shell_pid as pid_t;
-    }
+    let pid = foreground_process_id(master_fd, shell_pid);
 
     #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
     let link_path = format!("/proc/{pid}/cwd");
@@ -111,3 +108,51 @@ pub fn foreground_process_path(
 
     Ok(cwd)
 }
+
+/// Get executable name of controlling process.
+#[cfg(not(windows))]
+pub fn foreground_process_name(master_fd: RawFd, shell_pid: u32) -> Result<String, Box<dyn Error>> {
+    process_name(foreground_process_id(master_fd, shell_pid))
+}
+
+/// Get executable name of the foreground job, unless the shell itself is in the foreground.
+#[cfg(not(windows))]
+pub fn foreground_job_name(master_fd: RawFd, shell_pid: u32) -> Option<String> {
+    let pid = foreground_process_id(master_fd, shell_pid);
+    if pid == shell_pid as pid_t { None } else { process_name(pid).ok() }
+}
+
+/// Get executable name of a process.
+#[cfg(not(windows))]
+fn process_name(pid: pid_t) -> Result<String, Box<dyn Error>> {
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    let comm_path = format!("/proc/{pid}/comm");
+    #[cfg(target_os = "freebsd")]
+    let comm_path = format!("/compat/linux/proc/{}/comm", pid);
+
+    #[cfg(not(target_os = "macos"))]
+    let name = fs::read_to_string(comm_path)?.trim_end().to_owned();
+
+    #[cfg(target_os = "macos")]
+    let name = macos::proc::name(pid)?;
+
+    Ok(name)
+}
+
+/// Get ID of the PTY's foreground process group, falling back to the shell.
+#[cfg(not(windows))]
+pub fn foreground_process_id(master_fd: RawFd, shell_pid: u32) -> pid_t {
+    let pid = unsafe { libc::tcgetpgrp(master_fd) };
+    if pid < 0 { shell_pid as pid_t } else { pid }
+}
+
+/// Kill the foreground process group and the process group of the shell.
+#[cfg(not(windows))]
+pub fn kill_process_groups(foreground_pid: pid_t, shell_pid: u32) {
+    // Process group `0` is Alacritty's own, which is used for terminals without a shell.
+    for pid in [foreground_pid, shell_pid as pid_t].into_iter().filter(|pid| *pid > 0) {
+        unsafe {
+            libc::killpg(pid, libc::SIGKILL);
+        }
+    }
+}
@@ -302,6 +302,8 @@ fn merge_rects(lhs: Rect, rhs: Rect) -> Rect {
 mod tests {
     use super::*;
 
+    use alacritty_terminal::grid::Dimensions;
+
     #[test]
     fn damage_rect_math() {
         let rect_side = 10;
@@ -351,6 +353,22 @@ mod tests {
         assert_eq!(Rect::new(bound * 2 - cell_size, bound * 2 - cell_size / 2, 0, 0), rect);
     }
 
+    #[test]
+    fn gutter_damage_rect() {
+        let mut size_info = SizeInfo::new(100., 100., 4., 4., 2., 2., false);
+        size_info.reserve_columns(3);
+        assert_eq!(size_info.columns(), 21);
+        assert_eq!(size_info.padding_right(), 2.);
+        assert_eq!(size_info.without_gutter(), SizeInfo::new(100., 100., 4., 4., 2., 2., false));
+
+        // Damage starts after the gutter.
+        let size_info: SizeInfo<u32> = size_info.into();
+        let term_damage = [LineDamageBounds::new(0, 0, 1)];
+        let damage =
+            RenderDamageIterator::new(TermDamageIterator::new(&term_damage, 0), &size_info);
+        assert_eq!(damage.rect_for_line(term_damage[0]), Rect::new(14, 94, 8, 4));
+    }
+
     #[test]
     fn add_viewport_damage() {
         let mut frame_damage = FrameDamage::default();
@@ -3,6 +3,7 @@
 
 use std::cmp;
 use std::fmt::{self, Formatter};
+use std::iter;
 use std::mem::{self, ManuallyDrop};
 use std::num::NonZeroU32;
 use std::ops::Deref;
@@ -39,6 +40,7 @@ use alacritty_terminal::vte::ansi::{CursorShape, NamedColor};
 use crate::config::UiConfig;
 use crate::config::debug::RendererPreference;
 use crate::config::font::Font;
+use crate::config::scrolling::LineNumbers;
 use crate::config::window::Dimensions;
 #[cfg(not(windows))]
 use crate::config::window::StartupMode;
@@ -51,6 +53,12 @@ use crate::display::hint::{HintMatch, HintState};
 use crate::display::meter::Meter;
 use crate::display::window::Window;
 use crate::event::{Event, EventType, Mouse, SearchState};
+use crate::input::close::CloseConfirmation;
+use crate::input::keyboard::KeySequenceState;
+use crate::input::macros::InputMacro;
+use crate::input::palette::CommandPalette;
+use crate::input::paste::PasteConfirmation;
+use crate::input::vi::ViCommandState;
 use crate::message_bar::{MessageBuffer, MessageType};
 use crate::renderer::rects::{RenderLine, RenderLines, RenderRect};
 use crate::renderer::{self, GlyphCache, Renderer, platform};
@@ -61,6 +69,7 @@ pub mod color;
 pub mod content;
 pub mod cursor;
 pub mod hint;
+pub mod title;
 pub mod window;
 
 mod bell;
@@ -166,6 +175,10 @@ pub struct SizeInfo<T = f32> {
 
     /// Number of columns in the viewport.
     columns: usize,
+
+    /// Width of the gutter between the left padding and the grid.
+    #[serde(default)]
+    gutter: T,
 }
 
 impl From<SizeInfo<f32>> for SizeInfo<u32> {
@@ -179,6 +192,7 @@ impl From<SizeInfo<f32>> for SizeInfo<u32> {
             padding_y: size_info.padding_y as u32,
             screen_lines: size_info.screen_lines,
             columns: size_info.screen_lines,
+            gutter: size_info.gutter as u32,
         }
     }
 }
@@ -257,6 +271,7 @@ impl SizeInfo<f32> {
             padding_y: padding_y.floor(),
             screen_lines,
             columns,
+            gutter: 0.,
         }
     }
 
@@ -265,6 +280,41 @@ impl SizeInfo<f32> {
         self.screen_lines = cmp::max(self.screen_lines.saturating_sub(count), MIN_SCREEN_LINES);
     }
 
+    /// Reserve columns left of the grid for the line number gutter.
+    ///
+    /// The gutter is part of the left padding, so the grid starts after it.
+    #[inline]
+    pub fn reserve_columns(&mut self, count: usize) {
+        let count = cmp::min(count, self.columns.saturating_sub(MIN_COLUMNS));
+        let gutter = count as f32 * self.cell_width;
+        self.columns -= count;
+        self.padding_x += gutter;
+        self.gutter += gutter;
+    }
+
+    /// Number of columns reserved for the line number gutter.
+    #[inline]
+    pub fn gutter_columns(&self) -> usize {
+        (self.gutter / self.cell_width).round() as usize
+    }
+
+    /// Padding right of the grid, which doesn't include the gutter.
+    #[inline]
+    pub fn padding_right(&self) -> f32 {
+        self.padding_x - self.gutter
+    }
+
+    /// Size with the gutter added back to the grid, to draw inside of it.
+    #[inline]
+    pub fn without_gutter(&self) -> Self {
+        Self {
+            padding_x: self.padding_right(),
+            columns: self.columns + self.gutter_columns(),
+            gutter: 0.,
+            ..*self
+        }
+    }
+
     /// Check if coordinates are inside the terminal grid.
     ///
     /// The padding, message bar or search are not counted as part of the grid.
@@ -367,6 +417,33 @@ pub struct Display {
     /// State of the keyboard hints.
     pub hint_state: HintState,
 
+    /// Paste waiting for confirmation by the user.
+    pub paste_confirmation: Option<PasteConfirmation>,
+
+    /// Window close waiting for confirmation by the user.
+    pub close_confirmation: Option<CloseConfirmation>,
+
+    /// Exit code of the last shell process.
+    pub exit_code: Option<i32>,
+
+    /// Shell process exited while the window is held open.
+    pub child_exited: bool,
+
+    /// Last title set by the shell.
+    pub shell_title: Option<String>,
+
+    /// Open command palette.
+    pub command_palette: Option<CommandPalette>,
+
+    /// Pending keys of multi-key bindings.
+    pub key_sequence_state: KeySequenceState,
+
+    /// Active user-defined binding mode.
+    pub user_mode: Option<String>,
+
+    /// Keyboard input recorded for replay.
+    pub input_macro: InputMacro,
+
     /// Unprocessed display updates.
     pub pending_update: DisplayUpdate,
 
@@ -448,7 +525,7 @@ impl Display {
         let viewport_size = window.inner_size();
 
         // Create new size with at least one column and row.
-        let size_info = SizeInfo::new(
+        let mut size_info = SizeInfo::new(
             viewport_size.width as f32,
             viewport_size.height as f32,
             cell_width,
@@ -457,6 +534,7 @@ impl Display {
             padding.1,
             config.window.dynamic_padding && config.window.dimensions().is_none(),
         );
+        size_info.reserve_columns(line_number_columns(config, size_info.screen_lines()));
 
         info!("Cell size: {cell_width} x {cell_height}");
         info!("Padding: {} x {}", size_info.padding_x(), size_info.padding_y());
@@ -535,6 +613,15 @@ impl Display {
             font_size,
             window,
             pending_renderer_update: Default::default(),
+            paste_confirmation: Default::default(),
+            close_confirmation: Default::default(),
+            exit_code: Default::default(),
+            child_exited: Default::default(),
+            shell_title: Default::default(),
+            command_palette: Default::default(),
+            key_sequence_state: Default::default(),
+            user_mode: Default::default(),
+            input_macro: Default::default(),
             vi_highlighted_hint_age: Default::default(),
             highlighted_hint_age: Default::default(),
             vi_highlighted_hint: Default::default(),
@@ -709,6 +796,7 @@ impl Display {
         let message_bar_lines = message_buffer.message().map_or(0, |m| m.text(&new_size).len());
         let search_lines = usize::from(search_active);
         new_size.reserve_lines(message_bar_lines + search_lines);
+        new_size.reserve_columns(line_number_columns(config, new_size.screen_lines()));
 
         // Update resize increments.
         if config.window.resize_increments {
@@ -784,6 +872,7 @@ impl Display {
         message_buffer: &MessageBuffer,
         config: &UiConfig,
         search_state: &mut SearchState,
+        vi_command_state: &ViCommandState,
     ) {
         // Collect renderable content before the terminal is dropped.
         let mut content = RenderableContent::new(config, self, &terminal, search_state);
@@ -799,6 +888,8 @@ impl Display {
 
         let cursor_point = terminal.grid().cursor.point;
         let total_lines = terminal.grid().total_lines();
+        // Number lines from the oldest compressed line, which doesn't move when lines are restored.
+        let numbered_history = terminal.grid().history_size() + terminal.grid().compressed_lines();
         let metrics = self.glyph_cache.font_metrics();
         let size_info = self.size_info;
 
@@ -892,13 +983,48 @@ impl Display {
                 .filter(|point| point.line == -(display_offset as i32))
                 .map(|point| point.column);
             self.draw_line_indicator(config, total_lines, obstructed_column, line);
+
+            self.draw_line_numbers(config, vi_cursor_point, display_offset, numbered_history);
+
+            if let Some(line_input) = vi_command_state.line_input() {
+                self.draw_line_prompt(config, &format!(":{line_input}"));
+            }
         } else if search_state.regex().is_some() {
             // Show current display offset in vi-less search to indicate match position.
             self.draw_line_indicator(config, total_lines, None, display_offset);
         };
 
-        // Draw cursor.
-        rects.extend(cursor.rects(&size_info, config.cursor.thickness()));
+        // Indicate exited shells, pending keys of multi-key bindings, user modes and recording.
+        if self.child_exited {
+            let status = match self.exit_code {
+                Some(code) => format!("Process exited with code {code}"),
+                None => String::from("Process exited"),
+            };
+            self.draw_line_prompt(config, &format!("{status}  [Enter] Rerun  [Escape] Close"));
+        } else if self.key_sequence_state.is_pending() {
+            let text = format!("{} …", self.key_sequence_state.text());
+            self.draw_line_prompt(config, &text);
+        } else if let Some(user_mode) = &self.user_mode {
+            let text = format!("-- {} --", user_mode.to_uppercase());
+            self.draw_line_prompt(config, &text);
+        } else if self.input_macro.is_recording() {
+            self.draw_line_prompt(config, "-- RECORDING --");
+        }
+
+        // Draw cursor, unless it's obstructed by an overlay.
+        if let Some(paste_confirmation) = &self.paste_confirmation {
+            let lines = paste_confirmation.lines(size_info.columns().saturating_sub(2));
+            self.draw_overlay(config, &lines);
+        } else if let Some(close_confirmation) = &self.close_confirmation {
+            let lines = close_confirmation.lines(size_info.columns().saturating_sub(2));
+            self.draw_overlay(config, &lines);
+        } else if let Some(command_palette) = &self.command_palette {
+            let columns = size_info.columns().saturating_sub(2);
+            let lines = command_palette.lines(columns, size_info.screen_lines().saturating_sub(2));
+            self.draw_overlay(config, &lines);
+        } else {
+            rects.extend(cursor.rects(&size_info, config.cursor.thickness()));
+        }
 
         // Push visual bell after url/underline/strikeout rects.
         let visual_bell_intensity = self.visual_bell.intensity();
@@ -1382,6 +1508,106 @@ impl Display {
         }
     }
 
+    /// Draw vi mode line numbers into the gutter left of the grid.
+    #[inline(never)]
+    fn draw_line_numbers(
+        &mut self,
+        config: &UiConfig,
+        vi_cursor_point: Point,
+        display_offset: usize,
+        numbered_history: usize,
+    ) {
+        let mode = config.scrolling.line_numbers;
+        let columns = self.size_info.gutter_columns();
+        if mode == LineNumbers::None || columns == 0 {
+            return;
+        }
+
+        // Leave one column between the numbers and the grid.
+        let width = columns.saturating_sub(1);
+        let screen_lines = self.size_info.screen_lines();
+
+        let colors = &config.colors;
+        let fg = colors.line_indicator.foreground.unwrap_or(colors.primary.background);
+        let bg = colors.line_indicator.background.unwrap_or(colors.primary.foreground);
+
+        // Render into the gutter, which is part of the padding.
+        let size_info = self.size_info.without_gutter();
+        self.renderer.resize(&size_info);
+
+        for viewport_line in 0..screen_lines {
+            let line = Line(viewport_line as i32 - display_offset as i32);
+            let number = match mode {
+                LineNumbers::Relative if line != vi_cursor_point.line => {
+                    (line - vi_cursor_point.line).0.unsigned_abs() as usize
+                },
+                _ => (line.0 as isize + numbered_history as isize) as usize + 1,
+            };
+
+            // Only show the last digits of numbers exceeding the gutter.
+            let text = format!("{number:>width$} ");
+            let text = &text[text.len() - columns..];
+            let point = Point::new(viewport_line, Column(0));
+
+            let glyph_cache = &mut self.glyph_cache;
+            self.renderer.draw_string(point, fg, bg, text.chars(), &size_info, glyph_cache);
+        }
+
+        self.renderer.resize(&self.size_info);
+
+        // Damage tracking only covers the grid, so redraw everything for current and next frame.
+        self.damage_tracker.frame().mark_fully_damaged();
+        self.damage_tracker.next_frame().mark_fully_damaged();
+    }
+
+    /// Draw a prompt over the last line of the viewport.
+    #[inline(never)]
+    fn draw_line_prompt(&mut self, config: &UiConfig, prompt: &str) {
+        let num_cols = self.size_info.columns();
+        let text = format!("{prompt:<num_cols$}");
+        let point = Point::new(self.size_info.screen_lines() - 1, Column(0));
+
+        // Damage the prompt for current and next frame.
+        let damage = LineDamageBounds::new(point.line, 0, num_cols - 1);
+        self.damage_tracker.frame().damage_line(damage);
+        self.damage_tracker.next_frame().damage_line(damage);
+
+        let fg = config.colors.footer_bar_foreground();
+        let bg = config.colors.footer_bar_background();
+        let glyph_cache = &mut self.glyph_cache;
+        self.renderer.draw_string(point, fg, bg, text.chars(), &self.size_info, glyph_cache);
+    }
+
+    /// Draw lines of text in a box centered above the terminal content.
+    fn draw_overlay(&mut self, config: &UiConfig, lines: &[String]) {
+        let num_cols = self.size_info.columns();
+        let num_lines = self.size_info.screen_lines();
+
+        // Surround the text with one cell of padding.
+        let text_width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let width = cmp::min(text_width + 2, num_cols);
+        let height = cmp::min(lines.len() + 2, num_lines);
+        let left = (num_cols - width) / 2;
+        let top = (num_lines - height) / 2;
+
+        let fg = config.colors.footer_bar_foreground();
+        let bg = config.colors.footer_bar_background();
+        let empty = String::new();
+        for (i, line) in iter::once(&empty).chain(lines).chain([&empty]).take(height).enumerate() {
+            let point = Point::new(top + i, Column(left));
+            let text = format!(" {line:<0$}", width - 1);
+
+            // Damage the overlay for current and next frame.
+            let damage = LineDamageBounds::new(point.line, left, left + width - 1);
+            self.damage_tracker.frame().damage_line(damage);
+            self.damage_tracker.next_frame().damage_line(damage);
+
+            let glyph_cache = &mut self.glyph_cache;
+            let chars = text.chars().take(width);
+            self.renderer.draw_string(point, fg, bg, chars, &self.size_info, glyph_cache);
+        }
+    }
+
     /// Highlight damaged rects.
     ///
     /// This function is for debug purposes only.
@@ -1630,11 +1856,26 @@ fn window_size(
 ) -> PhysicalSize<u32> {
     let padding = config.window.padding(scale_factor);
 
-    let grid_width = cell_width * dimensions.columns.max(MIN_COLUMNS) as f32;
-    let grid_height = cell_height * dimensions.lines.max(MIN_SCREEN_LINES) as f32;
+    let lines = dimensions.lines.max(MIN_SCREEN_LINES);
+    let columns = dimensions.columns.max(MIN_COLUMNS) + line_number_columns(config, lines);
+    let grid_width = cell_width * columns as f32;
+    let grid_height = cell_height * lines as f32;
 
     let width = (padding.0).mul_add(2., grid_width).floor();
     let height = (padding.1).mul_add(2., grid_height).floor();
 
     PhysicalSize::new(width as u32, height as u32)
 }
+
+/// Number of columns reserved for vi mode line numbers, including a separating column.
+///
+/// Absolute numbers reserve space for the configured history, larger numbers only show their
+/// last digits.
+fn line_number_columns(config: &UiConfig, screen_lines: usize) -> usize {
+    let max_number = match config.scrolling.line_numbers {
+        LineNumbers::None => return 0,
+        LineNumbers::Absolute => config.scrolling.history() as usize + screen_lines,
+        LineNumbers::Relative => screen_lines,
+    };
+    max_number.to_string().len() + 1
+}
@@ -0,0 +1,311 @@
+//! Window titles generated from the `window.title_template` config.
+
+#[cfg(not(windows))]
+use std::os::unix::io::RawFd;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use alacritty_terminal::grid::Dimensions;
+use alacritty_terminal::term::Term;
+
+use crate::config::UiConfig;
+use crate::display::Display;
+use crate::string::{ShortenDirection, StrShortener};
+
+/// Minimum time between updates of templated titles caused by terminal output.
+pub const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Character used to indicate truncated titles.
+const SHORTENER: char = '…';
+
+/// Characters of separators which are dropped next to empty placeholders.
+const SEPARATORS: &[char] = &['—', '–', '-', '|', ':', '·', '•', ',', '@', '/'];
+
+/// Values of the title template's placeholders.
+#[derive(Default, Debug)]
+pub struct TitleContext<'a> {
+    /// Name of the foreground process.
+    pub process: Option<String>,
+
+    /// Working directory of the foreground process.
+    pub cwd: Option<String>,
+
+    /// Terminal dimensions.
+    pub columns: usize,
+    pub lines: usize,
+
+    /// Exit code of the exited shell.
+    pub exit_code: Option<i32>,
+
+    /// Title set by the shell.
+    pub title: Option<&'a str>,
+
+    /// Local user name.
+    pub user: Option<&'a str>,
+
+    /// Host reported through OSC 7, or the local host name.
+    pub host: Option<&'a str>,
+}
+
+impl<'a> TitleContext<'a> {
+    pub fn new(
+        display: &'a Display,
+        reported_host: Option<&'a str>,
+        #[cfg(not(windows))] master_fd: RawFd,
+        #[cfg(not(windows))] shell_pid: u32,
+    ) -> Self {
+        // Avoid querying the exited shell's process.
+        #[cfg(not(windows))]
+        let (process, cwd) = if display.child_exited {
+            (None, None)
+        } else {
+            let process = crate::daemon::foreground_process_name(master_fd, shell_pid).ok();
+            let cwd = crate::daemon::foreground_process_path(master_fd, shell_pid)
+                .ok()
+                .map(|path| crate::path_util::shorten_home(&path.to_string_lossy()));
+            (process, cwd)
+        };
+        #[cfg(windows)]
+        let (process, cwd) = (None, None);
+
+        let (user, local_host) = user_and_host();
+        let host = reported_host.filter(|host| !host.is_empty() && *host != "localhost");
+
+        Self {
+            process,
+            cwd,
+            columns: display.size_info.columns(),
+            lines: display.size_info.screen_lines(),
+            exit_code: display.exit_code,
+            title: display.shell_title.as_deref(),
+            user,
+            host: host.or(local_host),
+        }
+    }
+
+    /// Get the value of a placeholder.
+    fn value(&self, placeholder: &str) -> Option<String> {
+        let value = match placeholder {
+            "process" => self.process.clone().unwrap_or_default(),
+            "cwd" => self.cwd.clone().unwrap_or_default(),
+            "cols" => self.columns.to_string(),
+            "lines" => self.lines.to_string(),
+            "exit" => self.exit_code.map(|code| code.to_string()).unwrap_or_default(),
+            "title" => self.title.unwrap_or_default().into(),
+            "user" => self.user.unwrap_or_default().into(),
+            "host" => self.host.unwrap_or_default().into(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+/// Update the window title from the configured template.
+///
+/// Returns `false` when the title isn't generated from a template.
+pub fn update_templated_title<T>(
+    config: &UiConfig,
+    display: &mut Display,
+    terminal: &Term<T>,
+    #[cfg(not(windows))] master_fd: RawFd,
+    #[cfg(not(windows))] shell_pid: u32,
+) -> bool {
+    let host = terminal.working_directory().map(|directory| directory.host.as_str());
+    let title = templated_title(
+        config,
+        display,
+        host,
+        #[cfg(not(windows))]
+        master_fd,
+        #[cfg(not(windows))]
+        shell_pid,
+    );
+
+    match title {
+        Some(title) => {
+            if display.window.title() != title {
+                display.window.set_title(title);
+            }
+            true
+        },
+        None => false,
+    }
+}
+
+/// Generate the window title from the configured template.
+///
+/// Returns `None` when no template is configured.
+fn templated_title(
+    config: &UiConfig,
+    display: &Display,
+    reported_host: Option<&str>,
+    #[cfg(not(windows))] master_fd: RawFd,
+    #[cfg(not(windows))] shell_pid: u32,
+) -> Option<String> {
+    let template = config.window.title_template.as_ref()?;
+    let context = TitleContext::new(
+        display,
+        reported_host,
+        #[cfg(not(windows))]
+        master_fd,
+        #[cfg(not(windows))]
+        shell_pid,
+    );
+    Some(format_title(template, &context, config.window.title_max_width))
+}
+
+/// Substitute the placeholders of a title template.
+///
+/// Unknown placeholders are kept as-is and titles wider than `max_width` are truncated, unless
+/// `max_width` is zero.
+pub fn format_title(template: &str, context: &TitleContext<'_>, max_width: usize) -> String {
+    let mut parts = Vec::new();
+
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        parts.push(TitlePart::Text(&rest[..start]));
+        rest = &rest[start..];
+
+        let value = rest.find('}').and_then(|end| Some((end, context.value(&rest[1..end])?)));
+        match value {
+            Some((end, value)) => {
+                parts.push(TitlePart::Value(value));
+                rest = &rest[end + 1..];
+            },
+            None => {
+                parts.push(TitlePart::Text("{"));
+                rest = &rest[1..];
+            },
+        }
+    }
+    parts.push(TitlePart::Text(rest));
+
+    // Drop separators left over by empty placeholders, preferring the one in front of them.
+    for index in 0..parts.len() {
+        if !matches!(&parts[index], TitlePart::Value(value) if value.is_empty()) {
+            continue;
+        }
+
+        let leading = parts[..index].iter().all(|part| part.as_str().trim().is_empty());
+        let separator = if leading { index + 1 } else { index - 1 };
+        if let Some(TitlePart::Text(text)) = parts.get_mut(separator)
+            && text.chars().all(|c| c.is_whitespace() || SEPARATORS.contains(&c))
+        {
+            *text = "";
+        }
+    }
+
+    let title: String = parts.iter().map(TitlePart::as_str).collect();
+    let title = title.trim();
+
+    if max_width == 0 {
+        return title.into();
+    }
+
+    StrShortener::new(title, max_width, ShortenDirection::Right, Some(SHORTENER)).collect()
+}
+
+/// Literal text or substituted placeholder of a title template.
+enum TitlePart<'a> {
+    Text(&'a str),
+    Value(String),
+}
+
+impl TitlePart<'_> {
+    fn as_str(&self) -> &str {
+        match self {
+            Self::Text(text) => text,
+            Self::Value(value) => value,
+        }
+    }
+}
+
+/// Local user and host name.
+fn user_and_host() -> (Option<&'static str>, Option<&'static str>) {
+    static USER_AND_HOST: OnceLock<(Option<String>, Option<String>)> = OnceLock::new();
+
+    let (user, host) = USER_AND_HOST.get_or_init(|| {
+        let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+        (user, hostname())
+    });
+
+    (user.as_deref(), host.as_deref())
+}
+
+#[cfg(not(windows))]
+fn hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: The buffer is valid for its whole length.
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) } != 0 {
+        return None;
+    }
+
+    let len = buf.iter().position(|&byte| byte == 0).unwrap_or(buf.len());
+    Some(String::from_utf8_lossy(&buf[..len]).into_owned())
+}
+
+#[cfg(windows)]
+fn hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TitleContext<'static> {
+        TitleContext {
+            process: Some("vim".into()),
+            cwd: Some("~/src".into()),
+            columns: 80,
+            lines: 24,
+            title: Some("notes.md"),
+            user: Some("root"),
+            host: Some("box"),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn format_placeholders() {
+        let title = format_title("{process} — {cwd} [{cols}x{lines}]", &context(), 0);
+        assert_eq!(title, "vim — ~/src [80x24]");
+
+        let title = format_title("{user}@{host}: {title}", &context(), 0);
+        assert_eq!(title, "root@box: notes.md");
+    }
+
+    #[test]
+    fn format_exit_status() {
+        let mut context = context();
+        assert_eq!(format_title("{cwd} {exit}", &context, 0), "~/src");
+
+        context.exit_code = Some(1);
+        assert_eq!(format_title("{cwd} {exit}", &context, 0), "~/src 1");
+    }
+
+    #[test]
+    fn drop_separators_of_empty_placeholders() {
+        let mut context = context();
+        context.process = None;
+        assert_eq!(format_title("{process} — {cwd}", &context, 0), "~/src");
+        assert_eq!(format_title("{cwd} — {process}", &context, 0), "~/src");
+        assert_eq!(format_title("{user}@{process}: {title}", &context, 0), "root: notes.md");
+        assert_eq!(format_title("{process} | {exit} | {cwd}", &context, 0), "~/src");
+
+        // Other text is kept.
+        assert_eq!(format_title("{cwd} ({process})", &context, 0), "~/src ()");
+    }
+
+    #[test]
+    fn keep_unknown_placeholders() {
+        let title = format_title("{unknown} {{process}} {process", &context(), 0);
+        assert_eq!(title, "{unknown} {vim} {process");
+    }
+
+    #[test]
+    fn truncate_title() {
+        let title = format_title("{process} — {cwd}", &context(), 8);
+        assert_eq!(title, "vim — ~…");
+    }
+}
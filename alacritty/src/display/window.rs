@@ -44,6 +44,7 @@ use alacritty_terminal::index::Point;
 
 use crate::cli::WindowOptions;
 use crate::config::UiConfig;
+use crate::config::terminal::OnExit;
 use crate::config::window::{Decorations, Identity, WindowConfig};
 use crate::display::SizeInfo;
 #[cfg(target_os = "macos")]
@@ -116,11 +117,17 @@ pub struct Window {
     /// Hold the window when terminal exits.
     pub hold: bool,
 
+    /// Hold the window when terminal exits with a non-zero status.
+    pub hold_on_error: bool,
+
     window: WinitWindow,
 
     /// Current window title.
     title: String,
 
+    /// Alert of the output monitoring shown in the title.
+    alert: Option<MonitorAlert>,
+
     is_x11: bool,
     current_mouse_cursor: CursorIcon,
     mouse_visible: bool,
@@ -237,9 +244,11 @@ impl Window {
         let is_x11 = matches!(window.window_handle().unwrap().as_raw(), RawWindowHandle::Xlib(_));
 
         Ok(Self {
-            hold: options.terminal_options.hold,
+            hold: options.terminal_options.hold || config.terminal.on_exit == OnExit::Hold,
+            hold_on_error: config.terminal.on_exit == OnExit::HoldOnError,
             requested_redraw: false,
             title: identity.title,
+            alert: None,
             current_mouse_cursor,
             mouse_visible: true,
             has_frame: true,
@@ -274,12 +283,24 @@ impl Window {
         self.window.set_outer_position(position);
     }
 
-    #[cfg(target_os = "macos")]
+    #[inline]
+    pub fn outer_position(&self) -> Option<PhysicalPosition<i32>> {
+        self.window.outer_position().ok()
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         self.window.focus_window();
     }
 
+    /// Lower the window to the bottom of the stacking order.
+    ///
+    /// This is only supported on macOS.
+    pub fn lower(&self) {
+        #[cfg(target_os = "macos")]
+        self.order_below(0);
+    }
+
     /// macOS: 将窗口置前并激活应用。
     #[cfg(target_os = "macos")]
     pub fn order_front_and_activate(&self) {
@@ -418,7 +439,7 @@ impl Window {
     #[inline]
     pub fn set_title(&mut self, title: String) {
         self.title = title;
-        self.window.set_title(&self.title);
+        self.update_title();
     }
 
     /// Get the window title.
@@ -427,6 +448,27 @@ impl Window {
         &self.title
     }
 
+    /// Set the alert of the output monitoring, which is indicated in the title.
+    pub fn set_alert(&mut self, alert: Option<MonitorAlert>) {
+        if self.alert != alert {
+            self.alert = alert;
+            self.update_title();
+        }
+    }
+
+    /// Get the alert of the output monitoring.
+    #[inline]
+    pub fn alert(&self) -> Option<MonitorAlert> {
+        self.alert
+    }
+
+    fn update_title(&self) {
+        match self.alert {
+            Some(alert) => self.window.set_title(&format!("{} {}", alert.indicator(), self.title)),
+            None => self.window.set_title(&self.title),
+        }
+    }
+
     #[inline]
     pub fn request_redraw(&mut self) {
         if !self.requested_redraw {
@@ -550,6 +592,10 @@ impl Window {
         self.window.set_minimized(minimized);
     }
 
+    pub fn is_minimized(&self) -> bool {
+        self.window.is_minimized().unwrap_or(false)
+    }
+
     pub fn set_resize_increments(&self, increments: PhysicalSize<f32>) {
         self.window.set_resize_increments(Some(increments));
     }
@@ -688,3 +734,29 @@ fn use_srgb_color_space(window: &WinitWindow) {
 
     view.window().unwrap().setColorSpace(Some(&NSColorSpace::sRGBColorSpace()));
 }
+
+/// Alerts raised by the monitoring of a window's output.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MonitorAlert {
+    /// Output of an unfocused window.
+    Activity,
+    /// No output for the configured duration.
+    Silence,
+}
+
+impl MonitorAlert {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Activity => "activity",
+            Self::Silence => "silence",
+        }
+    }
+
+    /// Title prefix indicating the alert.
+    fn indicator(self) -> &'static str {
+        match self {
+            Self::Activity => "●",
+            Self::Silence => "◌",
+        }
+    }
+}
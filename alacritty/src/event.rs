@@ -26,6 +26,7 @@ use glutin::config::Config as GlutinConfig;
 use glutin::display::GetGlDisplay;
 use log::{debug, error, info, warn};
 use winit::application::ApplicationHandler;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event::{
     ElementState, Event as WinitEvent, Ime, Modifiers, MouseButton, StartCause,
     Touch as TouchEvent, WindowEvent,
@@ -41,6 +42,7 @@ use alacritty_terminal::index::{Boundary, Column, Direction, Line, Point, Side};
 use alacritty_terminal::selection::{Selection, SelectionType};
 use alacritty_terminal::term::cell::Flags;
 use alacritty_terminal::term::search::{Match, RegexSearch};
+use alacritty_terminal::term::window_ops::{WindowOp, WindowState};
 use alacritty_terminal::term::{self, ClipboardType, Term, TermMode};
 use alacritty_terminal::vte::ansi::NamedColor;
 
@@ -48,20 +50,27 @@ use alacritty_terminal::vte::ansi::NamedColor;
 use crate::cli::{IpcConfig, ParsedOptions};
 use crate::cli::{Options as CliOptions, WindowOptions};
 use crate::clipboard::Clipboard;
+#[cfg(unix)]
+use crate::config::Signal;
 use crate::config::ui_config::{HintAction, HintInternalAction};
-use crate::config::{self, UiConfig};
-#[cfg(not(windows))]
-use crate::daemon::foreground_process_path;
+use crate::config::{self, CopyOptions, UiConfig};
 use crate::daemon::spawn_daemon;
+#[cfg(not(windows))]
+use crate::daemon::{foreground_job_name, foreground_process_name, foreground_process_path};
 use crate::display::color::Rgb;
 use crate::display::hint::HintMatch;
+use crate::display::title;
 use crate::display::window::Window;
 use crate::display::{Display, Preedit, SizeInfo};
+use crate::input::close::CloseConfirmation;
+use crate::input::paste::PasteConfirmation;
+use crate::input::vi::{ViCommandState, ViMark};
 use crate::input::{self, ActionContext as _, FONT_SIZE_STEP};
 #[cfg(unix)]
 use crate::ipc::{self, SocketReply};
 use crate::logging::{LOG_TARGET_CONFIG, LOG_TARGET_WINIT};
 use crate::message_bar::{Message, MessageBuffer};
+use crate::pty_backend::PtyBackends;
 use crate::scheduler::{Scheduler, TimerId, Topic};
 use crate::window_context::WindowContext;
 
@@ -71,6 +80,9 @@ pub const TYPING_SEARCH_DELAY: Duration = Duration::from_millis(500);
 /// Maximum number of lines for the blocking search while still typing the search regex.
 const MAX_SEARCH_WHILE_TYPING: Option<usize> = Some(1000);
 
+/// Number of compressed lines restored at once while searching backward through them.
+const SEARCH_THAW_LINES: usize = 1000;
+
 /// Maximum number of search terms stored in the history.
 const MAX_SEARCH_HISTORY_SIZE: usize = 255;
 
@@ -94,6 +106,7 @@ pub struct Processor {
     windows: HashMap<WindowId, WindowContext, RandomState>,
     proxy: EventLoopProxy<Event>,
     gl_config: Option<GlutinConfig>,
+    pty_backends: PtyBackends,
     #[cfg(unix)]
     global_ipc_options: ParsedOptions,
     cli_options: CliOptions,
@@ -121,12 +134,18 @@ impl Processor {
     /// Create a new event processor.
     pub fn new(
         config: UiConfig,
-        cli_options: CliOptions,
+        mut cli_options: CliOptions,
         event_loop: &EventLoop<Event>,
     ) -> Processor {
         let proxy = event_loop.create_proxy();
         let scheduler = Scheduler::new(proxy.clone());
-        let initial_window_options = Some(cli_options.window_options.clone());
+        // Only the initial window is attached to the terminal passed through the CLI.
+        let mut initial_window_options = cli_options.window_options.clone();
+        #[cfg(unix)]
+        {
+            initial_window_options.terminal_options.attach_fd = cli_options.attach_fd.take();
+        }
+        let initial_window_options = Some(initial_window_options);
 
         // Disable all device events, since we don't care about them.
         event_loop.listen_device_events(DeviceEvents::Never);
@@ -152,6 +171,7 @@ impl Processor {
             proxy,
             scheduler,
             gl_config: None,
+            pty_backends: Default::default(),
             config: Rc::new(config),
             clipboard,
             windows: Default::default(),
@@ -374,6 +394,7 @@ impl Processor {
             self.proxy.clone(),
             self.config.clone(),
             window_options,
+            &self.pty_backends,
         )?;
 
         self.gl_config = Some(window_context.display.gl_context().config());
@@ -430,6 +451,7 @@ impl Processor {
             config,
             options,
             config_overrides,
+            &self.pty_backends,
         )?;
 
         let id = window_context.id();
@@ -637,6 +659,17 @@ impl ApplicationHandler<Event> for Processor {
                     ipc::send_reply(&mut stream, SocketReply::GetConfig(config_json));
                 }
             },
+            // Send signals to the foreground process of each window with matching ID.
+            #[cfg(unix)]
+            (EventType::Signal(signal), window_id) => {
+                for (_, window_context) in self
+                    .windows
+                    .iter()
+                    .filter(|(id, _)| window_id.is_none() || window_id == Some(*id))
+                {
+                    window_context.signal(signal);
+                }
+            },
             (EventType::ConfigReload(path), _) => {
                 // Clear config logs from message bar for all terminals.
                 for window_context in self.windows.values_mut() {
@@ -732,11 +765,11 @@ impl ApplicationHandler<Event> for Processor {
 
                 if self.gl_config.is_none() {
                     // Handle initial window creation in daemon mode.
-                    if let Err(err) = self.create_initial_window(event_loop, options) {
+                    if let Err(err) = self.create_initial_window(event_loop, *options) {
                         self.initial_window_error = Some(err);
                         event_loop.exit();
                     }
-                } else if let Err(err) = self.create_window(event_loop, options) {
+                } else if let Err(err) = self.create_window(event_loop, *options) {
                     error!("Could not open window: {err:?}");
                 }
             },
@@ -756,21 +789,30 @@ impl ApplicationHandler<Event> for Processor {
             },
             (EventType::Terminal(TerminalEvent::Wakeup), Some(window_id)) => {
                 if let Some(window_context) = self.windows.get_mut(window_id) {
+                    window_context.write_scrollback_log();
+                    window_context.monitor_output(&mut self.scheduler);
+                    window_context.schedule_title_update(&mut self.scheduler);
                     window_context.dirty = true;
                     if window_context.display.window.has_frame {
                         window_context.display.window.request_redraw();
                     }
                 }
             },
+            // NOTE: The exit code must be known before the `Exit` event is handled.
+            (EventType::Terminal(TerminalEvent::ChildExit(code)), Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(window_id) {
+                    window_context.display.exit_code = Some(code);
+                }
+            },
             (EventType::Terminal(TerminalEvent::Exit), Some(window_id)) => {
                 // Remove the closed terminal.
                 let window_context = match self.windows.entry(*window_id) {
                     // Don't exit when terminal exits if user asked to hold the window.
-                    Entry::Occupied(window_context)
-                        if !window_context.get().display.window.hold =>
-                    {
-                        window_context.remove()
+                    Entry::Occupied(mut window_context) if window_context.get().holds_on_exit() => {
+                        window_context.get_mut().child_exited();
+                        return;
                     },
+                    Entry::Occupied(window_context) => window_context.remove(),
                     _ => return,
                 };
 
@@ -804,6 +846,23 @@ impl ApplicationHandler<Event> for Processor {
                     }
                 }
             },
+            (EventType::UpdateTitle, Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(window_id) {
+                    window_context.update_templated_title();
+                }
+            },
+            (EventType::CheckSilence, Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(window_id) {
+                    window_context.check_silence(&mut self.scheduler);
+                }
+            },
+            (EventType::Respawn, Some(window_id)) => {
+                if let Some(window_context) = self.windows.get_mut(window_id) {
+                    if let Err(err) = window_context.respawn() {
+                        error!("Could not respawn shell: {err}");
+                    }
+                }
+            },
             // NOTE: This event bypasses batching to minimize input latency.
             (EventType::Frame, Some(window_id)) => {
                 if let Some(window_context) = self.windows.get_mut(window_id) {
@@ -978,14 +1037,24 @@ pub enum EventType {
     ToggleAllWindows,
     /// Unconditionally show all windows (used after creating a new window from menu).
     ShowAllWindows,
-    CreateWindow(WindowOptions),
+    CreateWindow(Box<WindowOptions>),
     #[cfg(unix)]
     IpcConfig(IpcConfig),
     #[cfg(unix)]
     IpcGetConfig(Arc<UnixStream>),
+    /// Send a signal to the foreground process of the terminal.
+    #[cfg(unix)]
+    Signal(Signal),
     BlinkCursor,
     BlinkCursorTimeout,
     SearchNext,
+    KeySequenceTimeout,
+    /// Restart the shell of a window in place.
+    Respawn,
+    /// Check a window for silence of its output.
+    CheckSilence,
+    /// Update the templated title of a window.
+    UpdateTitle,
     Frame,
 }
 
@@ -1109,6 +1178,7 @@ pub struct ActionContext<'a, N, T> {
     pub scheduler: &'a mut Scheduler,
     pub search_state: &'a mut SearchState,
     pub inline_search_state: &'a mut InlineSearchState,
+    pub vi_command_state: &'a mut ViCommandState,
     pub dirty: &'a mut bool,
     pub occluded: &'a mut bool,
     pub preserve_title: bool,
@@ -1184,6 +1254,13 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.clipboard.store(ty, text);
     }
 
+    fn copy_transformed_selection(&mut self, options: CopyOptions) {
+        let text = self.terminal.selection_to_string().map(|text| options.apply(&text));
+        if let Some(text) = text.filter(|text| !text.is_empty()) {
+            self.clipboard.store(ClipboardType::Clipboard, text);
+        }
+    }
+
     fn selection_is_empty(&self) -> bool {
         self.terminal.selection.as_ref().is_none_or(Selection::is_empty)
     }
@@ -1323,14 +1400,15 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             options.window_tabbing_id = tabbing_id;
         }
 
-        let _ = self.event_proxy.send_event(Event::new(EventType::CreateWindow(options), None));
+        let _ = self
+            .event_proxy
+            .send_event(Event::new(EventType::CreateWindow(Box::new(options)), None));
     }
 
     #[cfg(windows)]
     fn create_new_window(&mut self) {
-        let _ = self
-            .event_proxy
-            .send_event(Event::new(EventType::CreateWindow(WindowOptions::default()), None));
+        let _ =
+            self.event_proxy.send_event(Event::new(EventType::CreateWindow(Box::default()), None));
     }
 
     fn spawn_daemon<I, S>(&self, program: &str, args: I)
@@ -1387,6 +1465,9 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
 
         // Store original search position as origin and reset location.
         if self.terminal.mode().contains(TermMode::VI) {
+            let mark = ViMark::new(self.terminal, self.terminal.vi_mode_cursor.point);
+            self.vi_command_state.push_jump(mark);
+
             self.search_state.origin = self.terminal.vi_mode_cursor.point;
             self.search_state.display_offset_delta = 0;
 
@@ -1606,10 +1687,7 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
 
     /// Find the next search match.
     fn search_next(&mut self, origin: Point, direction: Direction, side: Side) -> Option<Match> {
-        self.search_state
-            .dfas
-            .as_mut()
-            .and_then(|dfas| self.terminal.search_next(dfas, origin, direction, side, None))
+        self.search_history(origin, direction, side, None)
     }
 
     #[inline]
@@ -1622,6 +1700,11 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.search_state.history_index.is_some()
     }
 
+    #[inline]
+    fn child_exited(&self) -> bool {
+        self.display.child_exited
+    }
+
     /// Handle keyboard typing start.
     ///
     /// This will temporarily disable some features like terminal cursor blinking or the mouse
@@ -1710,8 +1793,8 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             ClickState::None => return,
             _ if control => SelectionType::Block,
             ClickState::Click => SelectionType::Simple,
-            ClickState::DoubleClick => SelectionType::Semantic,
-            ClickState::TripleClick => SelectionType::Lines,
+            ClickState::DoubleClick => self.config.selection.double_click.selection_type(),
+            ClickState::TripleClick => self.config.selection.triple_click.selection_type(),
         };
 
         // Load mouse point, treating message bar and padding as the closest cell.
@@ -1801,40 +1884,95 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
             }
         } else if self.inline_search_state.char_pending {
             self.inline_search_input(text);
-        } else if bracketed && self.terminal().mode().contains(TermMode::BRACKETED_PASTE) {
-            self.on_terminal_input_start();
+        } else if let Some(confirmation) = self.paste_confirmation(text, bracketed) {
+            self.display.paste_confirmation = Some(confirmation);
+            self.display.damage_tracker.frame().mark_fully_damaged();
+            *self.dirty = true;
+        } else {
+            self.write_paste(text, bracketed);
+        }
+    }
 
-            self.write_to_pty(&b"\x1b[200~"[..]);
+    fn confirm_paste(&mut self, confirmed: bool) {
+        let confirmation = match self.display.paste_confirmation.take() {
+            Some(confirmation) => confirmation,
+            None => return,
+        };
 
-            // Write filtered escape sequences.
-            //
-            // We remove `\x1b` to ensure it's impossible for the pasted text to write the bracketed
-            // paste end escape `\x1b[201~` and `\x03` since some shells incorrectly terminate
-            // bracketed paste when they receive it.
-            let filtered = text.replace(['\x1b', '\x03'], "");
-            self.write_to_pty(filtered.into_bytes());
+        if confirmed {
+            self.write_paste(&confirmation.text, confirmation.bracketed);
+        }
 
-            self.write_to_pty(&b"\x1b[201~"[..]);
-        } else {
-            self.on_terminal_input_start();
+        self.display.damage_tracker.frame().mark_fully_damaged();
+        *self.dirty = true;
+    }
 
-            let payload = if bracketed {
-                // In non-bracketed (ie: normal) mode, terminal applications cannot distinguish
-                // pasted data from keystrokes.
-                //
-                // In theory, we should construct the keystrokes needed to produce the data we are
-                // pasting... since that's neither practical nor sensible (and probably an
-                // impossible task to solve in a general way), we'll just replace line breaks
-                // (windows and unix style) with a single carriage return (\r, which is what the
-                // Enter key produces).
-                text.replace("\r\n", "\r").replace('\n', "\r").into_bytes()
-            } else {
-                // When we explicitly disable bracketed paste don't manipulate with the input,
-                // so we pass user input as is.
-                text.to_owned().into_bytes()
-            };
+    fn close(&mut self) {
+        let confirmation = (!self.display.child_exited).then(|| self.close_confirmation());
+        if let Some(confirmation) = confirmation.flatten() {
+            self.display.close_confirmation = Some(confirmation);
+            self.display.damage_tracker.frame().mark_fully_damaged();
+            *self.dirty = true;
+            return;
+        }
 
-            self.write_to_pty(payload);
+        // User asked to close the window, so no need to hold it.
+        self.display.window.hold = false;
+        self.display.window.hold_on_error = false;
+        self.terminal.exit();
+    }
+
+    fn confirm_close(&mut self, confirmed: bool) {
+        if self.display.close_confirmation.take().is_none() {
+            return;
+        }
+
+        if confirmed {
+            self.display.window.hold = false;
+            self.display.window.hold_on_error = false;
+            self.terminal.exit();
+        }
+
+        self.display.damage_tracker.frame().mark_fully_damaged();
+        *self.dirty = true;
+    }
+
+    fn respawn(&mut self) {
+        let window_id = self.display.window.id();
+        let _ = self.event_proxy.send_event(Event::new(EventType::Respawn, window_id));
+    }
+
+    #[cfg(unix)]
+    fn signal(&mut self, signal: Signal) {
+        let window_id = self.display.window.id();
+        let _ = self.event_proxy.send_event(Event::new(EventType::Signal(signal), window_id));
+    }
+
+    #[inline]
+    fn user_mode(&self) -> Option<&str> {
+        self.display.user_mode.as_deref()
+    }
+
+    fn set_user_mode(&mut self, mode: Option<String>) {
+        self.display.user_mode = mode;
+        *self.dirty = true;
+    }
+
+    #[inline]
+    fn record_input(&mut self, input: &[u8]) {
+        self.display.input_macro.record(input);
+    }
+
+    fn toggle_macro_recording(&mut self) {
+        self.display.input_macro.toggle_recording();
+        *self.dirty = true;
+    }
+
+    fn play_macro(&mut self) {
+        let input = self.display.input_macro.recorded().to_vec();
+        if !input.is_empty() {
+            self.on_terminal_input_start();
+            self.write_to_pty(input);
         }
     }
 
@@ -1860,6 +1998,7 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.window().set_ime_allowed(was_in_vi_mode);
 
         self.terminal.toggle_vi_mode();
+        self.vi_command_state.reset();
 
         *self.dirty = true;
     }
@@ -1869,6 +2008,11 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
         self.inline_search_state
     }
 
+    /// Get vi command state.
+    fn vi_command_state(&mut self) -> &mut ViCommandState {
+        self.vi_command_state
+    }
+
     /// Start vi mode inline search.
     fn start_inline_search(&mut self, direction: Direction, stop_short: bool) {
         self.inline_search_state.stop_short = stop_short;
@@ -1928,6 +2072,157 @@ impl<'a, N: Notify + 'a, T: EventListener> input::ActionContext<T> for ActionCon
 }
 
 impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
+    /// Update the title from the configured template.
+    ///
+    /// Returns `false` when the title isn't generated from a template.
+    fn update_templated_title(&mut self) -> bool {
+        !self.preserve_title
+            && title::update_templated_title(
+                self.config,
+                self.display,
+                self.terminal,
+                #[cfg(not(windows))]
+                self.master_fd,
+                #[cfg(not(windows))]
+                self.shell_pid,
+            )
+    }
+
+    /// Current window state for xterm window reports.
+    fn window_state(&self) -> WindowState {
+        let window = &self.display.window;
+        let size_info = self.display.size_info;
+        let position = window.outer_position().unwrap_or_default();
+        let screen = window.current_monitor().map(|monitor| monitor.size()).unwrap_or_default();
+
+        WindowState {
+            iconified: window.is_minimized(),
+            x: position.x,
+            y: position.y,
+            screen_width: screen.width,
+            screen_height: screen.height,
+            cell_width: size_info.cell_width() as u32,
+            cell_height: size_info.cell_height() as u32,
+        }
+    }
+
+    /// Apply an xterm window operation requested by the terminal.
+    fn window_op(&mut self, op: WindowOp) {
+        let size_info = self.display.size_info;
+        let window = &self.display.window;
+
+        // Requested dimensions of the text area in pixels.
+        let (width, height) = match op {
+            WindowOp::ResizePixels { width, height } => {
+                (width.map(|width| width as f32), height.map(|height| height as f32))
+            },
+            WindowOp::ResizeCells { columns, lines } => (
+                columns.map(|columns| columns as f32 * size_info.cell_width()),
+                lines.map(|lines| lines as f32 * size_info.cell_height()),
+            ),
+            WindowOp::Iconify(iconify) => return window.set_minimized(iconify),
+            WindowOp::Move { x, y } => {
+                return window.set_outer_position(PhysicalPosition::new(x, y));
+            },
+            WindowOp::Raise => return window.focus_window(),
+            WindowOp::Lower => return window.lower(),
+            WindowOp::Maximize(maximize) => return window.set_maximized(maximize),
+            WindowOp::Fullscreen(Some(fullscreen)) => return window.set_fullscreen(fullscreen),
+            WindowOp::Fullscreen(None) => return window.toggle_fullscreen(),
+            WindowOp::Refresh => {
+                self.display.damage_tracker.frame().mark_fully_damaged();
+                *self.dirty = true;
+                return;
+            },
+            // Reports are handled by the terminal.
+            _ => return,
+        };
+
+        // Keep the padding around the text area.
+        let text_width = size_info.columns() as f32 * size_info.cell_width();
+        let text_height = size_info.screen_lines() as f32 * size_info.cell_height();
+        let width = size_info.width() + width.map_or(0., |width| width - text_width);
+        let height = size_info.height() + height.map_or(0., |height| height - text_height);
+
+        window.request_inner_size(PhysicalSize::new(width.max(1.) as u32, height.max(1.) as u32));
+    }
+
+    /// Write pasted text to the PTY.
+    fn write_paste(&mut self, text: &str, bracketed: bool) {
+        if bracketed && self.terminal().mode().contains(TermMode::BRACKETED_PASTE) {
+            self.on_terminal_input_start();
+
+            self.write_to_pty(&b"\x1b[200~"[..]);
+
+            // Write filtered escape sequences.
+            //
+            // We remove `\x1b` to ensure it's impossible for the pasted text to write the bracketed
+            // paste end escape `\x1b[201~` and `\x03` since some shells incorrectly terminate
+            // bracketed paste when they receive it.
+            let filtered = text.replace(['\x1b', '\x03'], "");
+            self.write_to_pty(filtered.into_bytes());
+
+            self.write_to_pty(&b"\x1b[201~"[..]);
+        } else {
+            self.on_terminal_input_start();
+
+            let payload = if bracketed {
+                // In non-bracketed (ie: normal) mode, terminal applications cannot distinguish
+                // pasted data from keystrokes.
+                //
+                // In theory, we should construct the keystrokes needed to produce the data we are
+                // pasting... since that's neither practical nor sensible (and probably an
+                // impossible task to solve in a general way), we'll just replace line breaks
+                // (windows and unix style) with a single carriage return (\r, which is what the
+                // Enter key produces).
+                text.replace("\r\n", "\r").replace('\n', "\r").into_bytes()
+            } else {
+                // When we explicitly disable bracketed paste don't manipulate with the input,
+                // so we pass user input as is.
+                text.to_owned().into_bytes()
+            };
+
+            self.write_to_pty(payload);
+        }
+    }
+
+    /// Check whether a paste needs to be confirmed by the user.
+    fn paste_confirmation(&self, text: &str, bracketed: bool) -> Option<PasteConfirmation> {
+        // Escapes sent by bindings are not pasted by the user.
+        if !bracketed {
+            return None;
+        }
+
+        let config = &self.config.terminal.paste;
+        let bracketed_mode = self.terminal.mode().contains(TermMode::BRACKETED_PASTE);
+        let confirmation = PasteConfirmation::new(config, text, bracketed, bracketed_mode)?;
+
+        // Skip confirmation for trusted foreground programs.
+        #[cfg(not(windows))]
+        if !config.trusted_programs.is_empty() {
+            if let Ok(name) = foreground_process_name(self.master_fd, self.shell_pid) {
+                if config.trusted_programs.contains(&name) {
+                    return None;
+                }
+            }
+        }
+
+        Some(confirmation)
+    }
+
+    /// Check whether closing the window requires confirmation.
+    #[cfg(not(windows))]
+    fn close_confirmation(&self) -> Option<CloseConfirmation> {
+        let program = foreground_job_name(self.master_fd, self.shell_pid)?;
+        CloseConfirmation::new(&self.config.terminal.close, &program)
+    }
+
+    /// Check whether closing the window requires confirmation.
+    #[cfg(windows)]
+    fn close_confirmation(&self) -> Option<CloseConfirmation> {
+        None
+    }
+
     fn update_search(&mut self) {
         let regex = match self.search_state.regex() {
             Some(regex) => regex,
@@ -1978,12 +2273,54 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         *self.dirty = true;
     }
 
+    /// Find the next search match, restoring compressed history when searching past its top.
+    fn search_history(
+        &mut self,
+        origin: Point,
+        direction: Direction,
+        side: Side,
+        limit: Option<usize>,
+    ) -> Option<Match> {
+        let dfas = self.search_state.dfas.as_mut()?;
+        let regex_match = self.terminal.search_next(dfas, origin, direction, side, limit);
+
+        // Backward searches past the top of the history wrap around to the bottom, so compressed
+        // lines are only searched if there was no match above the origin.
+        let match_point = |regex_match: &Match| match side {
+            Side::Left => *regex_match.start(),
+            Side::Right => *regex_match.end(),
+        };
+        let reached_top =
+            limit.is_none_or(|limit| origin.line - limit <= self.terminal.topmost_line());
+        if direction == Direction::Right
+            || !reached_top
+            || regex_match.as_ref().is_some_and(|regex_match| match_point(regex_match) <= origin)
+        {
+            return regex_match;
+        }
+
+        // Restore compressed lines in chunks, searching only the restored lines.
+        loop {
+            let topmost_line = self.terminal.topmost_line();
+            let thawed = self.terminal.grid_mut().thaw_history(SEARCH_THAW_LINES);
+            if thawed == 0 {
+                return regex_match;
+            }
+
+            let origin = Point::new(topmost_line - 1, self.terminal.last_column());
+            let limit = Some(thawed - 1);
+            let history_match = self.terminal.search_next(dfas, origin, direction, side, limit);
+            if let Some(history_match) = history_match.filter(|m| match_point(m) <= origin) {
+                return Some(history_match);
+            }
+        }
+    }
+
     /// Jump to the first regex match from the search origin.
     fn goto_match(&mut self, mut limit: Option<usize>) {
-        let dfas = match &mut self.search_state.dfas {
-            Some(dfas) => dfas,
-            None => return,
-        };
+        if self.search_state.dfas.is_none() {
+            return;
+        }
 
         // Limit search only when enough lines are available to run into the limit.
         limit = limit.filter(|&limit| limit <= self.terminal.total_lines());
@@ -1991,7 +2328,7 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
         // Jump to the next match.
         let direction = self.search_state.direction;
         let clamped_origin = self.search_state.origin.grid_clamp(self.terminal, Boundary::Grid);
-        match self.terminal.search_next(dfas, clamped_origin, direction, Side::Left, limit) {
+        match self.search_history(clamped_origin, direction, Side::Left, limit) {
             Some(regex_match) => {
                 let old_offset = self.terminal.grid().display_offset() as i32;
 
@@ -2043,6 +2380,11 @@ impl<'a, N: Notify + 'a, T: EventListener> ActionContext<'a, N, T> {
 
         // Clear focused match.
         self.search_state.focused_match = None;
+
+        // Compress restored history again, unless the search scrolled into it.
+        if self.terminal.grid().display_offset() == 0 {
+            self.terminal.freeze_history();
+        }
     }
 
     /// Update the cursor blinking state.
@@ -2208,6 +2550,7 @@ pub struct Mouse {
     pub accumulated_scroll: AccumulatedScroll,
     pub cell_side: Side,
     pub block_hint_launcher: bool,
+    pub drag_binding_triggered: bool,
     pub hint_highlight_dirty: bool,
     pub inside_text_area: bool,
     pub x: usize,
@@ -2226,6 +2569,7 @@ impl Default for Mouse {
             cell_side: Side::Left,
             hint_highlight_dirty: Default::default(),
             block_hint_launcher: Default::default(),
+            drag_binding_triggered: Default::default(),
             inside_text_area: Default::default(),
             accumulated_scroll: Default::default(),
             x: Default::default(),
@@ -2275,6 +2619,7 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
         match event {
             WinitEvent::UserEvent(Event { payload, .. }) => match payload {
                 EventType::SearchNext => self.ctx.goto_match(None),
+                EventType::KeySequenceTimeout => self.key_sequence_timeout(),
                 EventType::Scroll(scroll) => self.ctx.scroll(scroll),
                 EventType::BlinkCursor => {
                     // Only change state when timeout isn't reached, since we could get
@@ -2299,7 +2644,15 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                 },
                 EventType::Terminal(event) => match event {
                     TerminalEvent::Title(_title) => {
-                        if !self.ctx.preserve_title && self.ctx.config.window.dynamic_title {
+                        if self.ctx.config.window.dynamic_title {
+                            self.ctx.display.shell_title = Some(_title.clone());
+                        }
+
+                        let templated = self.ctx.update_templated_title();
+                        if !templated
+                            && !self.ctx.preserve_title
+                            && self.ctx.config.window.dynamic_title
+                        {
                             #[cfg(not(windows))]
                             {
                                 if let Ok(path) =
@@ -2323,8 +2676,11 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                         }
                     },
                     TerminalEvent::ResetTitle => {
+                        self.ctx.display.shell_title = None;
+                        let templated = self.ctx.update_templated_title();
+
                         let window_config = &self.ctx.config.window;
-                        if !self.ctx.preserve_title && window_config.dynamic_title {
+                        if !templated && !self.ctx.preserve_title && window_config.dynamic_title {
                             #[cfg(not(windows))]
                             {
                                 if let Ok(path) =
@@ -2397,27 +2753,31 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                         let text = format(self.ctx.size_info().into());
                         self.ctx.write_to_pty(text.into_bytes());
                     },
+                    TerminalEvent::WindowStateRequest(format) => {
+                        let text = format(self.ctx.window_state());
+                        self.ctx.write_to_pty(text.into_bytes());
+                    },
+                    TerminalEvent::WindowOp(op) => self.ctx.window_op(op),
                     TerminalEvent::PtyWrite(text) => self.ctx.write_to_pty(text.into_bytes()),
                     TerminalEvent::MouseCursorDirty => self.reset_mouse_cursor(),
                     TerminalEvent::CursorBlinkingChange => self.ctx.update_cursor_blinking(),
                     TerminalEvent::Exit | TerminalEvent::ChildExit(_) | TerminalEvent::Wakeup => (),
                 },
                 #[cfg(unix)]
-                EventType::IpcConfig(_) | EventType::IpcGetConfig(..) => (),
+                EventType::IpcConfig(_) | EventType::IpcGetConfig(..) | EventType::Signal(_) => (),
                 EventType::Message(_)
                 | EventType::ConfigReload(_)
                 | EventType::CreateWindow(_)
                 | EventType::ToggleAllWindows
                 | EventType::ShowAllWindows
+                | EventType::Respawn
+                | EventType::CheckSilence
+                | EventType::UpdateTitle
                 | EventType::Frame => (),
             },
             WinitEvent::WindowEvent { event, .. } => {
                 match event {
-                    WindowEvent::CloseRequested => {
-                        // User asked to close the window, so no need to hold it.
-                        self.ctx.window().hold = false;
-                        self.ctx.terminal.exit();
-                    },
+                    WindowEvent::CloseRequested => self.ctx.close(),
                     WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                         let old_scale_factor =
                             mem::replace(&mut self.ctx.window().scale_factor, scale_factor);
@@ -2469,10 +2829,16 @@ impl input::Processor<EventProxy, ActionContext<'_, Notifier, EventProxy>> {
                         // Reset the urgency hint when gaining focus.
                         if is_focused {
                             self.ctx.window().set_urgent(false);
+                            self.ctx.window().set_alert(None);
                             // 聚焦时同步窗口标题为当前前台进程工作目录（忽略 dynamic_title/preserve_title）。
+                            #[cfg(windows)]
+                            self.ctx.update_templated_title();
                             #[cfg(not(windows))]
-                            if let Ok(path) =
-                                crate::daemon::foreground_process_path(self.ctx.master_fd, self.ctx.shell_pid)
+                            if !self.ctx.update_templated_title()
+                                && let Ok(path) = crate::daemon::foreground_process_path(
+                                    self.ctx.master_fd,
+                                    self.ctx.shell_pid,
+                                )
                             {
                                 let title = path.to_string_lossy().into_owned();
                                 let title = crate::path_util::shorten_home(&title);
@@ -0,0 +1,59 @@
+//! Confirmation of closing a window while a program is running.
+
+use crate::config::terminal::Close;
+use crate::input::truncate;
+
+/// Close request waiting for confirmation by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CloseConfirmation {
+    program: String,
+}
+
+impl CloseConfirmation {
+    /// Check whether closing requires confirmation.
+    ///
+    /// The `program` is the name of the foreground process, when it isn't the shell itself.
+    pub fn new(config: &Close, program: &str) -> Option<Self> {
+        if !config.confirm || config.ignored_programs.iter().any(|ignored| ignored == program) {
+            return None;
+        }
+
+        Some(Self { program: program.into() })
+    }
+
+    /// Text of the confirmation dialog, truncated to the number of columns.
+    pub fn lines(&self, columns: usize) -> Vec<String> {
+        let lines = [
+            String::from("Close window?"),
+            format!("`{}` is still running", self.program),
+            String::new(),
+            String::from("[Enter] Close  [Escape] Cancel"),
+        ];
+
+        lines.iter().map(|line| truncate(line, columns)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ignored_programs() {
+        let config = Close { ignored_programs: vec![String::from("tmux")], ..Default::default() };
+        assert!(CloseConfirmation::new(&config, "vim").is_some());
+        assert_eq!(CloseConfirmation::new(&config, "tmux"), None);
+
+        let config = Close { confirm: false, ..Default::default() };
+        assert_eq!(CloseConfirmation::new(&config, "vim"), None);
+    }
+
+    #[test]
+    fn dialog_lines() {
+        let confirmation = CloseConfirmation::new(&Default::default(), "htop").unwrap();
+        let lines = confirmation.lines(16);
+
+        assert_eq!(lines[0], "Close window?");
+        assert_eq!(lines[1], "`htop` is still…");
+    }
+}
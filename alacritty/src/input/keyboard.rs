@@ -1,6 +1,7 @@
 use std::borrow::Cow;
+use std::{iter, mem};
 
-use winit::event::{ElementState, KeyEvent};
+use winit::event::{ElementState, KeyEvent, Modifiers};
 #[cfg(target_os = "macos")]
 use winit::keyboard::ModifiersKeyState;
 use winit::keyboard::{Key, KeyLocation, ModifiersState, NamedKey};
@@ -12,7 +13,7 @@ use alacritty_terminal::term::TermMode;
 use winit::platform::modifier_supplement::KeyEventExtModifierSupplement;
 
 use crate::config::{Action, BindingKey, BindingMode, KeyBinding};
-use crate::event::TYPING_SEARCH_DELAY;
+use crate::event::{Event, EventType, TYPING_SEARCH_DELAY};
 use crate::input::{ActionContext, Execute, Processor};
 use crate::scheduler::{TimerId, Topic};
 
@@ -45,6 +46,32 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             return;
         }
 
+        // All key bindings are disabled while a paste is waiting for confirmation.
+        if self.ctx.display().paste_confirmation.is_some() {
+            match key.logical_key {
+                Key::Named(NamedKey::Enter) => self.ctx.confirm_paste(true),
+                Key::Named(NamedKey::Escape) => self.ctx.confirm_paste(false),
+                _ => (),
+            }
+            return;
+        }
+
+        // All key bindings are disabled while closing the window is waiting for confirmation.
+        if self.ctx.display().close_confirmation.is_some() {
+            match key.logical_key {
+                Key::Named(NamedKey::Enter) => self.ctx.confirm_close(true),
+                Key::Named(NamedKey::Escape) => self.ctx.confirm_close(false),
+                _ => (),
+            }
+            return;
+        }
+
+        // All key bindings are disabled while the command palette is open.
+        if self.ctx.display().command_palette.is_some() {
+            self.command_palette_input(&key, text);
+            return;
+        }
+
         // First key after inline search is captured.
         let inline_state = self.ctx.inline_search_state();
         if inline_state.char_pending {
@@ -52,11 +79,37 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             return;
         }
 
+        // Counts and arguments of vi commands are captured before bindings.
+        let vi_command = mode.contains(TermMode::VI) && !self.ctx.search_active();
+        if vi_command && self.vi_command_input(text, mods) {
+            return;
+        }
+
+        // Keys of multi-key bindings are captured before other bindings.
+        if self.key_sequence_input(&key) {
+            return;
+        }
+
         // Reset search delay when the user is still typing.
         self.reset_search_delay();
 
+        let suppress_chars = self.process_key_bindings(&key);
+
+        // Pending vi counts and operators only apply to the next key.
+        if vi_command && !Self::is_modifier_key(&key) {
+            self.ctx.vi_command_state().finish_key();
+        }
+
         // Key bindings suppress the character input.
-        if self.process_key_bindings(&key) {
+        if suppress_chars {
+            return;
+        }
+
+        // User-defined modes don't have any input, unbound `Escape` leaves the mode.
+        if self.ctx.user_mode().is_some() {
+            if key.logical_key == Key::Named(NamedKey::Escape) {
+                self.ctx.set_user_mode(None);
+            }
             return;
         }
 
@@ -97,6 +150,7 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             if !is_modifier_key {
                 self.ctx.on_terminal_input_start();
             }
+            self.ctx.record_input(&bytes);
             self.ctx.write_to_pty(bytes);
         }
     }
@@ -175,44 +229,22 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     /// The provided mode, mods, and key must match what is allowed by a binding
     /// for its action to be executed.
     fn process_key_bindings(&mut self, key: &KeyEvent) -> bool {
-        let mode = BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active());
+        let mode = self.ctx.binding_mode();
         let mods = self.ctx.modifiers().state();
+        let user_mode = self.ctx.user_mode().map(String::from);
 
         // Don't suppress char if no bindings were triggered.
         let mut suppress_chars = None;
 
-        // We don't want the key without modifier, because it means something else most of
-        // the time. However what we want is to manually lowercase the character to account
-        // for both small and capital letters on regular characters at the same time.
-        let logical_key = if let Key::Character(ch) = key.logical_key.as_ref() {
-            // Match `Alt` bindings without `Alt` being applied, otherwise they use the
-            // composed chars, which are not intuitive to bind.
-            //
-            // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
-            // preventing them from being used in bindings
-            //
-            // For more see https://github.com/rust-windowing/winit/issues/2945.
-            if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key()))
-                && mods.alt_key()
-            {
-                key.key_without_modifiers()
-            } else {
-                Key::Character(ch.to_lowercase().into())
-            }
-        } else {
-            key.logical_key.clone()
-        };
+        let logical_key = binding_logical_key(key, mods);
 
         // Get the action of a key binding.
         let mut binding_action = |binding: &KeyBinding| {
-            let key = match (&binding.trigger, &logical_key) {
-                (BindingKey::Scancode(_), _) => BindingKey::Scancode(key.physical_key),
-                (_, code) => {
-                    BindingKey::Keycode { key: code.clone(), location: key.location.into() }
-                },
-            };
+            let key = binding_input(&binding.trigger, key, &logical_key);
 
-            if binding.is_triggered_by(mode, mods, &key) {
+            if binding.is_triggered_by(mode, mods, &key)
+                && binding.in_user_mode(user_mode.as_deref())
+            {
                 // Pass through the key if any of the bindings has the `ReceiveChar` action.
                 *suppress_chars.get_or_insert(true) &= binding.action != Action::ReceiveChar;
 
@@ -247,6 +279,131 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         suppress_chars.unwrap_or(false)
     }
 
+    /// Handle key input to the command palette.
+    fn command_palette_input(&mut self, key: &KeyEvent, text: &str) {
+        let palette = match &mut self.ctx.display().command_palette {
+            Some(palette) => palette,
+            None => return,
+        };
+
+        let close = match key.logical_key.as_ref() {
+            Key::Named(NamedKey::Escape | NamedKey::Enter) => true,
+            Key::Named(NamedKey::ArrowUp) => {
+                palette.select_previous();
+                false
+            },
+            Key::Named(NamedKey::ArrowDown | NamedKey::Tab) => {
+                palette.select_next();
+                false
+            },
+            Key::Named(NamedKey::Backspace) => {
+                palette.backspace();
+                false
+            },
+            _ => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    palette.input(c);
+                }
+                false
+            },
+        };
+
+        self.ctx.mark_dirty();
+
+        if close {
+            let palette = self.ctx.display().command_palette.take();
+            let action = palette.as_ref().and_then(|palette| palette.selected_action());
+            if let Some(action) = action.filter(|_| key.logical_key == Key::Named(NamedKey::Enter))
+            {
+                action.execute(&mut self.ctx);
+            }
+        }
+    }
+
+    /// Attempt to continue or complete a key sequence binding.
+    ///
+    /// Returns `true` if the key was consumed by a key sequence.
+    fn key_sequence_input(&mut self, key: &KeyEvent) -> bool {
+        let state = &mut self.ctx.display().key_sequence_state;
+        if state.replaying {
+            return false;
+        } else if Self::is_modifier_key(key) {
+            return state.is_pending();
+        }
+
+        let mut keys = mem::take(&mut state.keys);
+        keys.push((key.clone(), *self.ctx.modifiers()));
+
+        let mode = self.ctx.binding_mode();
+        let user_mode = self.ctx.user_mode().map(String::from);
+        let mut action = None;
+        let mut partial = false;
+        for binding in self.ctx.config().key_bindings() {
+            if !binding.in_user_mode(user_mode.as_deref()) {
+                continue;
+            }
+
+            match sequence_matches(binding, mode, &keys) {
+                Some(true) => {
+                    action = Some(binding.action.clone());
+                    break;
+                },
+                Some(false) => partial = true,
+                None => (),
+            }
+        }
+
+        let timer_id = TimerId::new(Topic::KeySequence, self.ctx.window().id());
+        let was_pending = self.ctx.scheduler_mut().unschedule(timer_id).is_some();
+
+        if let Some(action) = action {
+            self.ctx.mark_dirty();
+            action.execute(&mut self.ctx);
+        } else if partial {
+            self.ctx.display().key_sequence_state.keys = keys;
+            self.ctx.mark_dirty();
+
+            let event = Event::new(EventType::KeySequenceTimeout, self.ctx.window().id());
+            let timeout = self.ctx.config().key_sequence_timeout();
+            self.ctx.scheduler_mut().schedule(event, timeout, false, timer_id);
+        } else if was_pending {
+            // Pass through the pending keys, the current key could start a new sequence.
+            let key = keys.pop();
+            self.replay_keys(keys);
+            return key.is_some_and(|(key, _)| self.key_sequence_input(&key));
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Process the keys of an incomplete key sequence as regular input.
+    pub fn key_sequence_timeout(&mut self) {
+        let keys = mem::take(&mut self.ctx.display().key_sequence_state.keys);
+        self.replay_keys(keys);
+    }
+
+    /// Run keys through input processing with the modifiers they were pressed with.
+    fn replay_keys(&mut self, keys: Vec<(KeyEvent, Modifiers)>) {
+        if keys.is_empty() {
+            return;
+        }
+
+        self.ctx.mark_dirty();
+
+        let modifiers = *self.ctx.modifiers();
+        self.ctx.display().key_sequence_state.replaying = true;
+
+        for (key, mods) in keys {
+            *self.ctx.modifiers() = mods;
+            self.key_input(key);
+        }
+
+        self.ctx.display().key_sequence_state.replaying = false;
+        *self.ctx.modifiers() = modifiers;
+    }
+
     /// Handle key release.
     fn key_release(&mut self, key: KeyEvent, mode: TermMode, mods: ModifiersState) {
         if !mode.contains(TermMode::REPORT_EVENT_TYPES)
@@ -287,6 +444,114 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     }
 }
 
+/// Keys of a partially entered key sequence binding.
+#[derive(Default, Debug)]
+pub struct KeySequenceState {
+    keys: Vec<(KeyEvent, Modifiers)>,
+
+    /// Pending keys are being processed as regular input.
+    replaying: bool,
+}
+
+impl KeySequenceState {
+    /// Check if a key sequence is waiting for its next key.
+    pub fn is_pending(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    /// Description of the pending keys, like `Ctrl+A`.
+    pub fn text(&self) -> String {
+        let keys = self
+            .keys
+            .iter()
+            .map(|(key, mods)| key_text(&key.key_without_modifiers(), mods.state()));
+        keys.collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Description of a key with its modifiers, like `Ctrl+A`.
+pub fn key_text(key: &Key, mods: ModifiersState) -> String {
+    let mut text = String::new();
+    for (modifier, name) in [
+        (ModifiersState::CONTROL, "Ctrl+"),
+        (ModifiersState::ALT, "Alt+"),
+        (ModifiersState::SHIFT, "Shift+"),
+        (ModifiersState::SUPER, "Super+"),
+    ] {
+        if mods.contains(modifier) {
+            text.push_str(name);
+        }
+    }
+
+    match key {
+        Key::Character(c) => text.push_str(&c.to_uppercase()),
+        Key::Named(named) => text.push_str(&format!("{named:?}")),
+        _ => text.push('?'),
+    }
+
+    text
+}
+
+/// Key used for matching bindings against a key press.
+fn binding_logical_key(key: &KeyEvent, mods: ModifiersState) -> Key {
+    // We don't want the key without modifier, because it means something else most of
+    // the time. However what we want is to manually lowercase the character to account
+    // for both small and capital letters on regular characters at the same time.
+    if let Key::Character(ch) = key.logical_key.as_ref() {
+        // Match `Alt` bindings without `Alt` being applied, otherwise they use the
+        // composed chars, which are not intuitive to bind.
+        //
+        // On Windows, the `Ctrl + Alt` mangles `logical_key` to unidentified values, thus
+        // preventing them from being used in bindings
+        //
+        // For more see https://github.com/rust-windowing/winit/issues/2945.
+        if (cfg!(target_os = "macos") || (cfg!(windows) && mods.control_key())) && mods.alt_key() {
+            key.key_without_modifiers()
+        } else {
+            Key::Character(ch.to_lowercase().into())
+        }
+    } else {
+        key.logical_key.clone()
+    }
+}
+
+/// Binding input of a key press, in the representation used by the binding's trigger.
+fn binding_input(trigger: &BindingKey, key: &KeyEvent, logical_key: &Key) -> BindingKey {
+    match trigger {
+        BindingKey::Scancode(_) => BindingKey::Scancode(key.physical_key),
+        _ => BindingKey::Keycode { key: logical_key.clone(), location: key.location.into() },
+    }
+}
+
+/// Match the entered keys against a key sequence binding.
+///
+/// Returns `Some(true)` when the sequence is complete and `Some(false)` when more keys are
+/// required.
+fn sequence_matches(
+    binding: &KeyBinding,
+    mode: BindingMode,
+    keys: &[(KeyEvent, Modifiers)],
+) -> Option<bool> {
+    if binding.sequence.is_empty()
+        || keys.len() > binding.sequence.len() + 1
+        || !binding.is_active(mode)
+    {
+        return None;
+    }
+
+    let mut triggers = iter::once((&binding.trigger, binding.mods))
+        .chain(binding.sequence.iter().map(|(trigger, mods)| (trigger, *mods)));
+    for ((trigger, trigger_mods), (key, mods)) in triggers.by_ref().zip(keys) {
+        let mods = mods.state();
+        let input = binding_input(trigger, key, &binding_logical_key(key, mods));
+        if *trigger != input || trigger_mods != mods {
+            return None;
+        }
+    }
+
+    Some(triggers.next().is_none())
+}
+
 /// Build a key's keyboard escape sequence based on the given `key`, `mods`, and `mode`.
 ///
 /// The key sequences for `APP_KEYPAD` and alike are handled inside the bindings.
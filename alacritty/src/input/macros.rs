@@ -0,0 +1,65 @@
+//! Recording and replay of keyboard input.
+
+/// Keyboard input recorded for replay.
+#[derive(Default, Debug)]
+pub struct InputMacro {
+    /// Input of the last finished recording.
+    recorded: Vec<u8>,
+
+    /// Input of the active recording.
+    recording: Option<Vec<u8>>,
+}
+
+impl InputMacro {
+    /// Check if keyboard input is being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Start recording, or finish the active recording.
+    ///
+    /// The previous macro is only replaced once the new recording is finished.
+    pub fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            Some(recording) => self.recorded = recording,
+            None => self.recording = Some(Vec::new()),
+        }
+    }
+
+    /// Append input to the active recording.
+    pub fn record(&mut self, input: &[u8]) {
+        if let Some(recording) = &mut self.recording {
+            recording.extend_from_slice(input);
+        }
+    }
+
+    /// Input of the last finished recording.
+    pub fn recorded(&self) -> &[u8] {
+        &self.recorded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_macro() {
+        let mut input_macro = InputMacro::default();
+        input_macro.record(b"ignored");
+
+        input_macro.toggle_recording();
+        input_macro.record(b"ls");
+        input_macro.record(b"\r");
+        assert!(input_macro.recorded().is_empty());
+
+        input_macro.toggle_recording();
+        input_macro.record(b"ignored");
+        assert_eq!(input_macro.recorded(), b"ls\r");
+
+        // Previous macro is kept until the new recording is done.
+        input_macro.toggle_recording();
+        input_macro.record(b"pwd");
+        assert_eq!(input_macro.recorded(), b"ls\r");
+    }
+}
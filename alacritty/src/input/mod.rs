@@ -30,7 +30,8 @@ use alacritty_terminal::event::EventListener;
 use alacritty_terminal::grid::{Dimensions, Scroll};
 use alacritty_terminal::index::{Boundary, Column, Direction, Point, Side};
 use alacritty_terminal::selection::SelectionType;
-use alacritty_terminal::term::search::Match;
+use alacritty_terminal::term::cell::Flags;
+use alacritty_terminal::term::search::{Match, RegexIter};
 use alacritty_terminal::term::{ClipboardType, Term, TermMode};
 use alacritty_terminal::vi_mode::ViMotion;
 use alacritty_terminal::vte::ansi::{ClearMode, Handler};
@@ -38,17 +39,27 @@ use alacritty_terminal::vte::ansi::{ClearMode, Handler};
 use crate::clipboard::Clipboard;
 #[cfg(target_os = "macos")]
 use crate::config::window::Decorations;
-use crate::config::{Action, BindingMode, MouseAction, SearchAction, UiConfig, ViAction};
+use crate::config::{
+    Action, BindingMode, CopyOptions, MouseAction, MouseBinding, MouseTrigger, SearchAction,
+    Signal, UiConfig, ViAction,
+};
 use crate::display::hint::HintMatch;
 use crate::display::window::Window;
 use crate::display::{Display, SizeInfo};
 use crate::event::{
     ClickState, Event, EventType, InlineSearchState, Mouse, TouchPurpose, TouchZoom,
 };
+use crate::input::palette::CommandPalette;
+use crate::input::vi::{PendingChar, ViCommandState};
 use crate::message_bar::{self, Message};
 use crate::scheduler::{Scheduler, TimerId, Topic};
 
+pub mod close;
 pub mod keyboard;
+pub mod macros;
+pub mod palette;
+pub mod paste;
+pub mod vi;
 
 /// Font size change interval in px.
 pub const FONT_SIZE_STEP: f32 = 1.;
@@ -82,6 +93,7 @@ pub trait ActionContext<T: EventListener> {
     fn mark_dirty(&mut self) {}
     fn size_info(&self) -> SizeInfo;
     fn copy_selection(&mut self, _ty: ClipboardType) {}
+    fn copy_transformed_selection(&mut self, _options: CopyOptions) {}
     fn start_selection(&mut self, _ty: SelectionType, _point: Point, _side: Side) {}
     fn toggle_selection(&mut self, _ty: SelectionType, _point: Point, _side: Side) {}
     fn update_selection(&mut self, _point: Point, _side: Side) {}
@@ -123,9 +135,16 @@ pub trait ActionContext<T: EventListener> {
     fn advance_search_origin(&mut self, _direction: Direction) {}
     fn search_direction(&self) -> Direction;
     fn search_active(&self) -> bool;
+    fn child_exited(&self) -> bool {
+        false
+    }
+    fn binding_mode(&self) -> BindingMode {
+        BindingMode::new(self.terminal().mode(), self.search_active(), self.child_exited())
+    }
     fn on_typing_start(&mut self) {}
     fn toggle_vi_mode(&mut self) {}
     fn inline_search_state(&mut self) -> &mut InlineSearchState;
+    fn vi_command_state(&mut self) -> &mut ViCommandState;
     fn start_inline_search(&mut self, _direction: Direction, _stop_short: bool) {}
     fn inline_search_next(&mut self) {}
     fn inline_search_input(&mut self, _text: &str) {}
@@ -136,6 +155,18 @@ pub trait ActionContext<T: EventListener> {
     fn semantic_word(&self, point: Point) -> String;
     fn on_terminal_input_start(&mut self) {}
     fn paste(&mut self, _text: &str, _bracketed: bool) {}
+    fn confirm_paste(&mut self, _confirmed: bool) {}
+    fn close(&mut self) {}
+    fn confirm_close(&mut self, _confirmed: bool) {}
+    fn respawn(&mut self) {}
+    fn signal(&mut self, _signal: Signal) {}
+    fn user_mode(&self) -> Option<&str> {
+        None
+    }
+    fn set_user_mode(&mut self, _mode: Option<String>) {}
+    fn record_input(&mut self, _input: &[u8]) {}
+    fn toggle_macro_recording(&mut self) {}
+    fn play_macro(&mut self) {}
     fn spawn_daemon<I, S>(&self, _program: &str, _args: I)
     where
         I: IntoIterator<Item = S> + Debug + Copy,
@@ -167,7 +198,10 @@ impl<T: EventListener> Execute<T> for Action {
     #[inline]
     fn execute<A: ActionContext<T>>(&self, ctx: &mut A) {
         match self {
-            Action::Esc(s) => ctx.paste(s, false),
+            Action::Esc(s) => {
+                ctx.record_input(s.as_bytes());
+                ctx.paste(s, false)
+            },
             Action::Command(program) => ctx.spawn_daemon(program.program(), program.args()),
             Action::Hint(hint) => {
                 ctx.display().hint_state.start(hint.clone());
@@ -184,7 +218,7 @@ impl<T: EventListener> Execute<T> for Action {
             },
             Action::ViMotion(motion) => {
                 ctx.on_typing_start();
-                ctx.terminal_mut().vi_motion(*motion);
+                vi::motion(ctx, *motion);
                 ctx.mark_dirty();
             },
             Action::Vi(ViAction::ToggleNormalSelection) => {
@@ -219,6 +253,7 @@ impl<T: EventListener> Execute<T> for Action {
                 };
 
                 if let Some(regex_match) = ctx.search_next(origin, direction, Side::Left) {
+                    vi::record_jump(ctx);
                     ctx.terminal_mut().vi_goto_point(*regex_match.start());
                     ctx.mark_dirty();
                 }
@@ -235,6 +270,7 @@ impl<T: EventListener> Execute<T> for Action {
                 };
 
                 if let Some(regex_match) = ctx.search_next(origin, direction, Side::Left) {
+                    vi::record_jump(ctx);
                     ctx.terminal_mut().vi_goto_point(*regex_match.start());
                     ctx.mark_dirty();
                 }
@@ -244,6 +280,7 @@ impl<T: EventListener> Execute<T> for Action {
                 let origin = terminal.vi_mode_cursor.point.sub(terminal, Boundary::None, 1);
 
                 if let Some(regex_match) = ctx.search_next(origin, Direction::Left, Side::Left) {
+                    vi::record_jump(ctx);
                     ctx.terminal_mut().vi_goto_point(*regex_match.start());
                     ctx.mark_dirty();
                 }
@@ -253,6 +290,7 @@ impl<T: EventListener> Execute<T> for Action {
                 let origin = terminal.vi_mode_cursor.point.add(terminal, Boundary::None, 1);
 
                 if let Some(regex_match) = ctx.search_next(origin, Direction::Right, Side::Right) {
+                    vi::record_jump(ctx);
                     ctx.terminal_mut().vi_goto_point(*regex_match.end());
                     ctx.mark_dirty();
                 }
@@ -278,6 +316,24 @@ impl<T: EventListener> Execute<T> for Action {
             Action::Vi(ViAction::InlineSearchBackwardShort) => {
                 ctx.start_inline_search(Direction::Left, true)
             },
+            Action::Vi(ViAction::Yank) => vi::yank(ctx),
+            Action::Vi(ViAction::SetMark) => {
+                ctx.vi_command_state().start_char_pending(PendingChar::SetMark);
+            },
+            Action::Vi(ViAction::JumpToMark) => {
+                let pending = PendingChar::JumpToMark { linewise: false };
+                ctx.vi_command_state().start_char_pending(pending);
+            },
+            Action::Vi(ViAction::JumpToMarkLine) => {
+                let pending = PendingChar::JumpToMark { linewise: true };
+                ctx.vi_command_state().start_char_pending(pending);
+            },
+            Action::Vi(ViAction::JumpBackward) => vi::jump(ctx, Direction::Left),
+            Action::Vi(ViAction::JumpForward) => vi::jump(ctx, Direction::Right),
+            Action::Vi(ViAction::GotoLine) => {
+                ctx.vi_command_state().start_line_input();
+                ctx.mark_dirty();
+            },
             Action::Vi(ViAction::InlineSearchNext) => ctx.inline_search_next(),
             Action::Vi(ViAction::InlineSearchPrevious) => ctx.inline_search_previous(),
             Action::Vi(ViAction::SemanticSearchForward | ViAction::SemanticSearchBackward) => {
@@ -319,6 +375,7 @@ impl<T: EventListener> Execute<T> for Action {
             Action::SearchForward => ctx.start_search(Direction::Right),
             Action::SearchBackward => ctx.start_search(Direction::Left),
             Action::Copy => ctx.copy_selection(ClipboardType::Clipboard),
+            Action::CopyWith(options) => ctx.copy_transformed_selection(*options),
             #[cfg(not(any(target_os = "macos", windows)))]
             Action::CopySelection => ctx.copy_selection(ClipboardType::Selection),
             Action::ClearSelection => ctx.clear_selection(),
@@ -326,10 +383,20 @@ impl<T: EventListener> Execute<T> for Action {
                 let text = ctx.clipboard_mut().load(ClipboardType::Clipboard);
                 ctx.paste(&text, true);
             },
+            Action::PasteWith(options) => {
+                let text = ctx.clipboard_mut().load(ClipboardType::Clipboard);
+                ctx.paste(&options.apply(&text), true);
+            },
+            Action::PasteText(text) => ctx.paste(text, true),
             Action::PasteSelection => {
                 let text = ctx.clipboard_mut().load(ClipboardType::Selection);
                 ctx.paste(&text, true);
             },
+            Action::ClipboardHistory => {
+                let palette = CommandPalette::clipboard_history(ctx.clipboard_mut().history());
+                ctx.display().command_palette = Some(palette);
+                ctx.mark_dirty();
+            },
             Action::ToggleFullscreen => ctx.window().toggle_fullscreen(),
             Action::ToggleMaximized => ctx.window().toggle_maximized(),
             #[cfg(target_os = "macos")]
@@ -341,10 +408,7 @@ impl<T: EventListener> Execute<T> for Action {
             #[cfg(not(target_os = "macos"))]
             Action::Hide => ctx.window().set_visible(false),
             Action::Minimize => ctx.window().set_minimized(true),
-            Action::Quit => {
-                ctx.window().hold = false;
-                ctx.terminal_mut().exit();
-            },
+            Action::Quit => ctx.close(),
             Action::IncreaseFontSize => ctx.change_font_size(FONT_SIZE_STEP),
             Action::DecreaseFontSize => ctx.change_font_size(-FONT_SIZE_STEP),
             Action::ResetFontSize => ctx.reset_font_size(),
@@ -379,6 +443,16 @@ impl<T: EventListener> Execute<T> for Action {
             Action::ScrollLineUp => ctx.scroll(Scroll::Delta(1)),
             Action::ScrollLineDown => ctx.scroll(Scroll::Delta(-1)),
             Action::ScrollToTop => {
+                if ctx.terminal().mode().contains(TermMode::VI) {
+                    // Like `{count}gg` in vi, a count selects the target line.
+                    if let Some(line) = ctx.vi_command_state().explicit_count() {
+                        vi::goto_line(ctx, line);
+                        return;
+                    }
+
+                    vi::record_jump(ctx);
+                }
+
                 ctx.scroll(Scroll::Top);
 
                 // Move vi mode cursor.
@@ -388,6 +462,16 @@ impl<T: EventListener> Execute<T> for Action {
                 ctx.mark_dirty();
             },
             Action::ScrollToBottom => {
+                if ctx.terminal().mode().contains(TermMode::VI) {
+                    // Like `{count}G` in vi, a count selects the target line.
+                    if let Some(line) = ctx.vi_command_state().explicit_count() {
+                        vi::goto_line(ctx, line);
+                        return;
+                    }
+
+                    vi::record_jump(ctx);
+                }
+
                 ctx.scroll(Scroll::Bottom);
 
                 // Move vi mode cursor.
@@ -401,9 +485,26 @@ impl<T: EventListener> Execute<T> for Action {
             },
             Action::ClearHistory => ctx.terminal_mut().clear_screen(ClearMode::Saved),
             Action::ClearLogNotice => ctx.pop_message(),
+            Action::EnterMode(name) => ctx.set_user_mode(Some(name.clone())),
+            Action::ExitMode => ctx.set_user_mode(None),
+            Action::Sequence(actions) => {
+                for action in actions {
+                    action.execute(ctx);
+                }
+            },
+            Action::CommandPalette => {
+                let mode = ctx.binding_mode();
+                let palette = CommandPalette::new(ctx.config(), mode, ctx.user_mode());
+                ctx.display().command_palette = Some(palette);
+                ctx.mark_dirty();
+            },
+            Action::RecordMacro => ctx.toggle_macro_recording(),
+            Action::PlayMacro => ctx.play_macro(),
             #[cfg(not(target_os = "macos"))]
             Action::CreateNewWindow => ctx.create_new_window(),
             Action::SpawnNewInstance => ctx.spawn_new_instance(),
+            Action::RespawnShell => ctx.respawn(),
+            Action::Signal(signal) => ctx.signal(*signal),
             #[cfg(target_os = "macos")]
             Action::CreateNewWindow => ctx.create_new_window(None),
             #[cfg(target_os = "macos")]
@@ -495,6 +596,11 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         // Don't launch URLs if mouse has moved.
         self.ctx.mouse_mut().block_hint_launcher = true;
 
+        // Drag bindings take over the entire gesture once triggered.
+        if cell_changed && self.drag_bindings_input() {
+            return;
+        }
+
         if (lmb_pressed || rmb_pressed)
             && (self.ctx.modifiers().state().shift_key() || !self.ctx.mouse_mode())
         {
@@ -514,6 +620,30 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         }
     }
 
+    /// Trigger bindings for dragging the mouse with a button held down.
+    ///
+    /// Returns `true` if the current drag gesture is handled by a binding.
+    fn drag_bindings_input(&mut self) -> bool {
+        if self.ctx.mouse().drag_binding_triggered {
+            return true;
+        }
+
+        let mouse = self.ctx.mouse();
+        let button = if mouse.left_button_state == ElementState::Pressed {
+            MouseButton::Left
+        } else if mouse.middle_button_state == ElementState::Pressed {
+            MouseButton::Middle
+        } else if mouse.right_button_state == ElementState::Pressed {
+            MouseButton::Right
+        } else {
+            return false;
+        };
+
+        let triggered = self.process_mouse_bindings(MouseTrigger::Drag(button));
+        self.ctx.mouse_mut().drag_binding_triggered = triggered;
+        triggered
+    }
+
     /// Check which side of a cell an X coordinate lies on.
     fn cell_side(&self, x: usize) -> Side {
         let size_info = self.ctx.size_info();
@@ -522,9 +652,10 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             x.saturating_sub(size_info.padding_x() as usize) % size_info.cell_width() as usize;
         let half_cell_width = (size_info.cell_width() / 2.0) as usize;
 
+        let padding_right = size_info.padding_right();
         let additional_padding =
-            (size_info.width() - size_info.padding_x() * 2.) % size_info.cell_width();
-        let end_of_grid = size_info.width() - size_info.padding_x() - additional_padding;
+            (size_info.width() - size_info.padding_x() - padding_right) % size_info.cell_width();
+        let end_of_grid = size_info.width() - padding_right - additional_padding;
 
         if cell_x > half_cell_width
             // Edge case when mouse leaves the window.
@@ -613,6 +744,8 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     }
 
     fn on_mouse_press(&mut self, button: MouseButton) {
+        self.ctx.mouse_mut().drag_binding_triggered = false;
+
         // Handle mouse mode.
         if !self.ctx.modifiers().state().shift_key() && self.ctx.mouse_mode() {
             self.ctx.mouse_mut().click_state = ClickState::None;
@@ -658,8 +791,15 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     fn on_left_click(&mut self, point: Point) {
         let side = self.ctx.mouse().cell_side;
         let control = self.ctx.modifiers().state().control_key();
+        let selection = &self.ctx.config().selection;
+        let (double_click, triple_click) = (selection.double_click, selection.triple_click);
+        let move_cursor = self.alt_click_moves_cursor();
 
         match self.ctx.mouse().click_state {
+            ClickState::Click if move_cursor => {
+                self.ctx.clear_selection();
+                self.move_cursor_to(point);
+            },
             ClickState::Click => {
                 // Don't launch URLs if this click cleared the selection.
                 self.ctx.mouse_mut().block_hint_launcher = !self.ctx.selection_is_empty();
@@ -675,11 +815,11 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             },
             ClickState::DoubleClick if !control => {
                 self.ctx.mouse_mut().block_hint_launcher = true;
-                self.ctx.start_selection(SelectionType::Semantic, point, side);
+                self.start_click_selection(double_click.selection_type(), point, side);
             },
             ClickState::TripleClick if !control => {
                 self.ctx.mouse_mut().block_hint_launcher = true;
-                self.ctx.start_selection(SelectionType::Lines, point, side);
+                self.start_click_selection(triple_click.selection_type(), point, side);
             },
             _ => (),
         };
@@ -691,6 +831,87 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         }
     }
 
+    /// Start a multi-click selection, preferring smart selection rules over semantic selection.
+    fn start_click_selection(&mut self, ty: SelectionType, point: Point, side: Side) {
+        let smart_match = match ty {
+            SelectionType::Semantic => self.smart_selection_at(point),
+            _ => None,
+        };
+
+        match smart_match {
+            Some(smart_match) => {
+                self.ctx.start_selection(SelectionType::Simple, *smart_match.start(), Side::Left);
+                self.ctx.update_selection(*smart_match.end(), Side::Right);
+            },
+            None => self.ctx.start_selection(ty, point, side),
+        }
+    }
+
+    /// Find the match of the first smart selection rule containing a point.
+    fn smart_selection_at(&self, point: Point) -> Option<Match> {
+        let term = self.ctx.terminal();
+        let start = term.line_search_left(point);
+        let end = term.line_search_right(point);
+
+        self.ctx.config().selection.smart_rules.iter().find_map(|rule| {
+            rule.regex
+                .with_compiled(|regex| {
+                    RegexIter::new(start, end, Direction::Right, term, regex)
+                        .take_while(|regex_match| *regex_match.start() <= point)
+                        .find(|regex_match| regex_match.contains(&point))
+                })
+                .flatten()
+        })
+    }
+
+    /// Check if a click should move the shell's cursor instead of selecting text.
+    fn alt_click_moves_cursor(&mut self) -> bool {
+        let mods = self.ctx.modifiers().state();
+        let mode = *self.ctx.terminal().mode();
+
+        self.ctx.config().mouse.alt_click_moves_cursor
+            && mods == ModifiersState::ALT
+            && !mode.intersects(TermMode::ALT_SCREEN | TermMode::VI)
+            && !self.ctx.search_active()
+            && self.ctx.terminal().grid().display_offset() == 0
+    }
+
+    /// Move the shell's cursor to a point on its current line using arrow keys.
+    ///
+    /// Only points within the cursor's line, including its wrapped continuations, are reachable.
+    fn move_cursor_to(&mut self, point: Point) {
+        let term = self.ctx.terminal();
+        let cursor = term.grid().cursor.point;
+        if point.line < term.line_search_left(cursor).line
+            || point.line > term.line_search_right(cursor).line
+        {
+            return;
+        }
+
+        // Count characters between the points, since wide characters are a single key press.
+        let (start, end) = if point < cursor { (point, cursor) } else { (cursor, point) };
+        let spacers = Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER;
+        let mut chars = 0;
+        let mut current = start;
+        while current < end {
+            if !term.grid()[current].flags.intersects(spacers) {
+                chars += 1;
+            }
+            current = current.add(term, Boundary::None, 1);
+        }
+
+        let key: &[u8] = match (point < cursor, term.mode().contains(TermMode::APP_CURSOR)) {
+            (true, true) => b"\x1bOD",
+            (true, false) => b"\x1b[D",
+            (false, true) => b"\x1bOC",
+            (false, false) => b"\x1b[C",
+        };
+
+        if chars > 0 {
+            self.ctx.write_to_pty(key.repeat(chars));
+        }
+    }
+
     fn on_mouse_release(&mut self, button: MouseButton) {
         if !self.ctx.modifiers().state().shift_key() && self.ctx.mouse_mode() {
             let code = match button {
@@ -726,6 +947,10 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
             MouseScrollDelta::LineDelta(columns, lines) => {
                 let new_scroll_px_x = columns * self.ctx.size_info().cell_width();
                 let new_scroll_px_y = lines * self.ctx.size_info().cell_height();
+                if self.wheel_bindings_input(new_scroll_px_y as f64) {
+                    return;
+                }
+
                 self.scroll_terminal(
                     new_scroll_px_x as f64,
                     new_scroll_px_y as f64,
@@ -747,6 +972,10 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                             lpos.x = 0.;
                         }
 
+                        if self.wheel_bindings_input(lpos.y) {
+                            return;
+                        }
+
                         self.scroll_terminal(lpos.x, lpos.y, multiplier as f64);
                     },
                     _ => (),
@@ -755,6 +984,32 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
         }
     }
 
+    /// Trigger wheel bindings once for every line scrolled.
+    ///
+    /// Returns `true` if the scroll is handled by a binding.
+    fn wheel_bindings_input(&mut self, new_scroll_y_px: f64) -> bool {
+        let trigger = match new_scroll_y_px {
+            delta if delta > 0. => MouseTrigger::WheelUp,
+            delta if delta < 0. => MouseTrigger::WheelDown,
+            _ => return false,
+        };
+
+        if self.triggered_mouse_bindings(trigger).is_empty() {
+            return false;
+        }
+
+        let height = f64::from(self.ctx.size_info().cell_height());
+        self.ctx.mouse_mut().accumulated_scroll.y += new_scroll_y_px;
+        let lines = (self.ctx.mouse().accumulated_scroll.y / height).abs() as usize;
+        self.ctx.mouse_mut().accumulated_scroll.y %= height;
+
+        for _ in 0..lines {
+            self.process_mouse_bindings(trigger);
+        }
+
+        true
+    }
+
     fn scroll_terminal(&mut self, new_scroll_x_px: f64, new_scroll_y_px: f64, multiplier: f64) {
         const MOUSE_WHEEL_UP: u8 = 64;
         const MOUSE_WHEEL_DOWN: u8 = 65;
@@ -1018,7 +1273,17 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
                 ElementState::Pressed => {
                     // Process mouse press before bindings to update the `click_state`.
                     self.on_mouse_press(button);
-                    self.process_mouse_bindings(button);
+                    self.process_mouse_bindings(MouseTrigger::Button(button));
+
+                    match self.ctx.mouse().click_state {
+                        ClickState::DoubleClick => {
+                            self.process_mouse_bindings(MouseTrigger::DoubleClick(button));
+                        },
+                        ClickState::TripleClick => {
+                            self.process_mouse_bindings(MouseTrigger::TripleClick(button));
+                        },
+                        ClickState::None | ClickState::Click => (),
+                    }
                 },
                 ElementState::Released => self.on_mouse_release(button),
             }
@@ -1029,31 +1294,50 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     ///
     /// The provided mode, mods, and key must match what is allowed by a binding
     /// for its action to be executed.
-    fn process_mouse_bindings(&mut self, button: MouseButton) {
-        let mode = BindingMode::new(self.ctx.terminal().mode(), self.ctx.search_active());
-        let mouse_mode = self.ctx.mouse_mode();
+    ///
+    /// Returns `true` if at least one binding was executed.
+    fn process_mouse_bindings(&mut self, trigger: MouseTrigger) -> bool {
+        let bindings = self.triggered_mouse_bindings(trigger);
+
+        for binding in &bindings {
+            binding.action.execute(&mut self.ctx);
+        }
+
+        !bindings.is_empty()
+    }
+
+    /// Find all mouse bindings triggered by an input in the current state.
+    fn triggered_mouse_bindings(&mut self, trigger: MouseTrigger) -> Vec<MouseBinding> {
         let mods = self.ctx.modifiers().state();
-        let mouse_bindings = self.ctx.config().mouse_bindings().to_owned();
+        let mode = self.ctx.binding_mode();
+        let mouse_mode = self.ctx.mouse_mode();
+        let user_mode = self.ctx.user_mode();
 
         // If mouse mode is active, also look for bindings without shift.
         let fallback_allowed = mouse_mode && mods.contains(ModifiersState::SHIFT);
-        let mut exact_match_found = false;
 
-        for binding in &mouse_bindings {
-            // Don't trigger normal bindings in mouse mode unless Shift is pressed.
-            if binding.is_triggered_by(mode, mods, &button) && (fallback_allowed || !mouse_mode) {
-                binding.action.execute(&mut self.ctx);
-                exact_match_found = true;
-            }
+        // Don't trigger normal bindings in mouse mode unless Shift is pressed.
+        if mouse_mode && !fallback_allowed {
+            return Vec::new();
         }
 
-        if fallback_allowed && !exact_match_found {
-            let fallback_mods = mods & !ModifiersState::SHIFT;
-            for binding in &mouse_bindings {
-                if binding.is_triggered_by(mode, fallback_mods, &button) {
-                    binding.action.execute(&mut self.ctx);
-                }
-            }
+        let triggered = |mods: ModifiersState| -> Vec<MouseBinding> {
+            self.ctx
+                .config()
+                .mouse_bindings()
+                .iter()
+                .filter(|binding| {
+                    binding.is_triggered_by(mode, mods, &trigger) && binding.in_user_mode(user_mode)
+                })
+                .cloned()
+                .collect()
+        };
+
+        let bindings = triggered(mods);
+        if fallback_allowed && bindings.is_empty() {
+            triggered(mods & !ModifiersState::SHIFT)
+        } else {
+            bindings
         }
     }
 
@@ -1138,6 +1422,15 @@ impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
     }
 }
 
+/// Limit text to a number of columns, replacing the last visible character with an ellipsis.
+pub fn truncate(text: &str, columns: usize) -> String {
+    if text.chars().count() > columns {
+        text.chars().take(columns.saturating_sub(1)).chain(['…']).collect()
+    } else {
+        text.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1147,6 +1440,7 @@ mod tests {
     use winit::window::WindowId;
 
     use alacritty_terminal::event::Event as TerminalEvent;
+    use alacritty_terminal::index::Line;
 
     use crate::config::Binding;
     use crate::message_bar::MessageBuffer;
@@ -1185,6 +1479,10 @@ mod tests {
             self.inline_search_state
         }
 
+        fn vi_command_state(&mut self) -> &mut ViCommandState {
+            unimplemented!();
+        }
+
         fn search_active(&self) -> bool {
             false
         }
@@ -1485,7 +1783,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_nomode_shiftmod_require_shift,
-        binding: Binding { trigger: KEY, mods: ModifiersState::SHIFT, action: Action::from("\x1b[1;2D"), mode: BindingMode::empty(), notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::SHIFT, action: Action::from("\x1b[1;2D"), mode: BindingMode::empty(), notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: true,
         mode: BindingMode::empty(),
         mods: ModifiersState::SHIFT,
@@ -1493,7 +1791,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_nomode_nomod_require_shift,
-        binding: Binding { trigger: KEY, mods: ModifiersState::SHIFT, action: Action::from("\x1b[1;2D"), mode: BindingMode::empty(), notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::SHIFT, action: Action::from("\x1b[1;2D"), mode: BindingMode::empty(), notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: false,
         mode: BindingMode::empty(),
         mods: ModifiersState::empty(),
@@ -1501,7 +1799,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_nomode_controlmod,
-        binding: Binding { trigger: KEY, mods: ModifiersState::CONTROL, action: Action::from("\x1b[1;5D"), mode: BindingMode::empty(), notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::CONTROL, action: Action::from("\x1b[1;5D"), mode: BindingMode::empty(), notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: true,
         mode: BindingMode::empty(),
         mods: ModifiersState::CONTROL,
@@ -1509,7 +1807,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_nomode_nomod_require_not_appcursor,
-        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1b[D"), mode: BindingMode::empty(), notmode: BindingMode::APP_CURSOR },
+        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1b[D"), mode: BindingMode::empty(), notmode: BindingMode::APP_CURSOR, user_mode: None, sequence: Vec::new() },
         triggers: true,
         mode: BindingMode::empty(),
         mods: ModifiersState::empty(),
@@ -1517,7 +1815,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_appcursormode_nomod_require_appcursor,
-        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: true,
         mode: BindingMode::APP_CURSOR,
         mods: ModifiersState::empty(),
@@ -1525,7 +1823,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_nomode_nomod_require_appcursor,
-        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: false,
         mode: BindingMode::empty(),
         mods: ModifiersState::empty(),
@@ -1533,7 +1831,7 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_appcursormode_appkeypadmode_nomod_require_appcursor,
-        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::empty(), action: Action::from("\x1bOD"), mode: BindingMode::APP_CURSOR, notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: true,
         mode: BindingMode::APP_CURSOR | BindingMode::APP_KEYPAD,
         mods: ModifiersState::empty(),
@@ -1541,9 +1839,46 @@ mod tests {
 
     test_process_binding! {
         name: process_binding_fail_with_extra_mods,
-        binding: Binding { trigger: KEY, mods: ModifiersState::SUPER, action: Action::from("arst"), mode: BindingMode::empty(), notmode: BindingMode::empty() },
+        binding: Binding { trigger: KEY, mods: ModifiersState::SUPER, action: Action::from("arst"), mode: BindingMode::empty(), notmode: BindingMode::empty(), user_mode: None, sequence: Vec::new() },
         triggers: false,
         mode: BindingMode::empty(),
         mods: ModifiersState::ALT | ModifiersState::SUPER,
     }
+
+    #[test]
+    fn smart_selection_rules() {
+        let mut clipboard = Clipboard::new_nop();
+        let mut cfg = UiConfig::default();
+        let rule = toml::from_str("regex = \"[a-z/]+:[0-9]+\"").unwrap();
+        cfg.selection.smart_rules.push(rule);
+        let size = SizeInfo::new(60.0, 21.0, 3.0, 3.0, 0., 0., false);
+
+        let mut terminal = Term::new(cfg.term_options(), &size, MockEventProxy);
+        for (i, c) in "ab src/x:12 cd".chars().enumerate() {
+            terminal.grid_mut()[Line(0)][Column(i)].c = c;
+        }
+
+        let mut mouse = Mouse::default();
+        let mut inline_search_state = InlineSearchState::default();
+        let mut message_buffer = MessageBuffer::default();
+
+        let context = ActionContext {
+            terminal: &mut terminal,
+            mouse: &mut mouse,
+            size_info: &size,
+            clipboard: &mut clipboard,
+            modifiers: Default::default(),
+            message_buffer: &mut message_buffer,
+            inline_search_state: &mut inline_search_state,
+            config: &cfg,
+        };
+
+        let processor = Processor::new(context);
+
+        let smart_match = processor.smart_selection_at(Point::new(Line(0), Column(5)));
+        let expected = Point::new(Line(0), Column(3))..=Point::new(Line(0), Column(10));
+        assert_eq!(smart_match, Some(expected));
+
+        assert_eq!(processor.smart_selection_at(Point::new(Line(0), Column(12))), None);
+    }
 }
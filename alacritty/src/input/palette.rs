@@ -0,0 +1,298 @@
+//! Command palette listing all actions and their bindings, or the clipboard history.
+
+use std::{cmp, iter};
+
+use serde::Deserialize;
+use toml::Value;
+use winit::keyboard::{Key, NativeKey};
+
+use crate::config::ui_config::{HintAction, Program};
+use crate::config::{Action, BindingKey, BindingMode, KeyBinding, UiConfig};
+use crate::input::keyboard::key_text;
+use crate::input::truncate;
+
+/// Maximum width of the palette in columns.
+const MAX_WIDTH: usize = 80;
+
+/// Actions which aren't listed in the palette.
+const HIDDEN_ACTIONS: &[&str] = &["None", "ReceiveChar", "CommandPalette"];
+
+/// Actions which only have an effect on other platforms.
+#[cfg(target_os = "macos")]
+const UNSUPPORTED_ACTIONS: &[&str] = &["CopySelection", "PasteSelection"];
+#[cfg(not(target_os = "macos"))]
+const UNSUPPORTED_ACTIONS: &[&str] = &[
+    "HideOtherApplications",
+    "ToggleSimpleFullscreen",
+    "CreateNewTab",
+    "SelectNextTab",
+    "SelectPreviousTab",
+    "SelectTab1",
+    "SelectTab2",
+    "SelectTab3",
+    "SelectTab4",
+    "SelectTab5",
+    "SelectTab6",
+    "SelectTab7",
+    "SelectTab8",
+    "SelectTab9",
+    "SelectLastTab",
+];
+
+/// Action listed in the command palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PaletteEntry {
+    name: String,
+    binding: String,
+    action: Action,
+}
+
+/// Fuzzy-filterable list of actions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandPalette {
+    entries: Vec<PaletteEntry>,
+
+    /// Indices of the entries matching the query, ordered by relevance.
+    matches: Vec<usize>,
+
+    query: String,
+    selected: usize,
+}
+
+impl CommandPalette {
+    /// Create a palette with all actions and the bindings available in the current mode.
+    pub fn new(config: &UiConfig, mode: BindingMode, user_mode: Option<&str>) -> Self {
+        let bindings: Vec<&KeyBinding> = config
+            .key_bindings()
+            .iter()
+            .filter(|binding| binding.is_active(mode) && binding.in_user_mode(user_mode))
+            .collect();
+        let binding_for = |action: &Action| {
+            let binding = bindings.iter().find(|binding| binding.action == *action);
+            binding.map(|binding| binding_text(binding)).unwrap_or_default()
+        };
+
+        let mut entries = Vec::new();
+        let names = Action::VARIANTS
+            .iter()
+            .filter(|name| !HIDDEN_ACTIONS.contains(name) && !UNSUPPORTED_ACTIONS.contains(name));
+        for name in names {
+            if let Ok(action) = Action::deserialize(Value::String(name.to_string())) {
+                let binding = binding_for(&action);
+                entries.push(PaletteEntry { name: name.to_string(), binding, action });
+            }
+        }
+
+        // Add bound actions which aren't part of the named actions.
+        for binding in &bindings {
+            let name = match &binding.action {
+                Action::Esc(chars) => format!("Chars \"{}\"", chars.escape_debug()),
+                Action::Command(program) => format!("Command {}", program_text(program)),
+                Action::EnterMode(mode) => format!("EnterMode {mode}"),
+                Action::Vi(_)
+                | Action::ViMotion(_)
+                | Action::Search(_)
+                | Action::Sequence(_)
+                | Action::CopyWith(_)
+                | Action::PasteWith(_)
+                | Action::Signal(_) => binding.action.to_string(),
+                _ => continue,
+            };
+
+            if entries.iter().all(|entry| entry.action != binding.action) {
+                let action = binding.action.clone();
+                entries.push(PaletteEntry { name, binding: binding_text(binding), action });
+            }
+        }
+
+        for hint in &config.hints.enabled {
+            let name = match &hint.action {
+                HintAction::Action(action) => format!("Hint {action:?}"),
+                HintAction::Command(program) => format!("Hint {}", program_text(program)),
+            };
+            let binding = hint.binding.as_ref().filter(|binding| {
+                let binding = binding.key_binding(hint);
+                binding.is_active(mode) && binding.in_user_mode(user_mode)
+            });
+            let binding = binding.map(|binding| binding_text(binding.key_binding(hint)));
+
+            let action = Action::Hint(hint.clone());
+            entries.push(PaletteEntry { name, binding: binding.unwrap_or_default(), action });
+        }
+
+        let mut palette = Self { matches: Vec::new(), query: String::new(), selected: 0, entries };
+        palette.update_matches();
+        palette
+    }
+
+    /// Create a picker pasting one of the clipboard history's entries.
+    pub fn clipboard_history<'a>(history: impl Iterator<Item = &'a str>) -> Self {
+        let entries = history
+            .map(|text| {
+                // Show the text on a single line.
+                let name = text
+                    .chars()
+                    .map(|c| match c {
+                        '\n' => '⏎',
+                        c if c.is_control() => ' ',
+                        c => c,
+                    })
+                    .collect();
+                let action = Action::PasteText(text.into());
+                PaletteEntry { name, binding: String::new(), action }
+            })
+            .collect();
+
+        let mut palette = Self { matches: Vec::new(), query: String::new(), selected: 0, entries };
+        palette.update_matches();
+        palette
+    }
+
+    /// Add a character to the filter query.
+    pub fn input(&mut self, c: char) {
+        self.query.push(c);
+        self.update_matches();
+    }
+
+    /// Remove the last character of the filter query.
+    pub fn backspace(&mut self) {
+        self.query.pop();
+        self.update_matches();
+    }
+
+    /// Select the next matching action.
+    pub fn select_next(&mut self) {
+        self.selected = cmp::min(self.selected + 1, self.matches.len().saturating_sub(1));
+    }
+
+    /// Select the previous matching action.
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Action of the selected entry.
+    pub fn selected_action(&self) -> Option<&Action> {
+        let index = self.matches.get(self.selected)?;
+        Some(&self.entries[*index].action)
+    }
+
+    /// Text of the palette, limited to the available columns and lines.
+    pub fn lines(&self, columns: usize, lines: usize) -> Vec<String> {
+        let width = cmp::min(columns, MAX_WIDTH);
+        let mut text = vec![format!(": {}", self.query), String::new()];
+
+        // Scroll the list to keep the selection visible.
+        let visible = cmp::max(lines.saturating_sub(text.len()), 1);
+        let start = (self.selected + 1).saturating_sub(visible);
+
+        for (i, index) in self.matches.iter().enumerate().skip(start).take(visible) {
+            let entry = &self.entries[*index];
+            let marker = if i == self.selected { '>' } else { ' ' };
+            let name_width = width.saturating_sub(entry.binding.chars().count() + 3);
+            let name = truncate(&entry.name, name_width);
+            text.push(format!("{marker} {name:<name_width$} {}", entry.binding));
+        }
+
+        if self.matches.is_empty() {
+            text.push(String::from("  No matching entries"));
+        }
+
+        text.iter().map(|line| truncate(line, width)).collect()
+    }
+
+    /// Filter and sort the entries using the query.
+    fn update_matches(&mut self) {
+        let mut matches: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| Some((fuzzy_score(&self.query, &entry.name)?, i)))
+            .collect();
+        matches.sort_by_key(|(score, _)| *score);
+
+        self.matches = matches.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+}
+
+/// Score of the query's characters appearing in order in the text, lower is better.
+fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let mut chars = text.chars().enumerate();
+    let mut last_index = None;
+    let mut score = 0;
+
+    for query_char in query.chars().filter(|c| !c.is_whitespace()) {
+        let query_char = query_char.to_lowercase();
+        let (index, _) = chars.find(|(_, c)| c.to_lowercase().eq(query_char.clone()))?;
+
+        // Penalize gaps between the matched characters.
+        score += match last_index {
+            Some(last_index) => index - last_index - 1,
+            None => index,
+        };
+        last_index = Some(index);
+    }
+
+    Some(score)
+}
+
+/// Description of a binding's keys.
+fn binding_text(binding: &KeyBinding) -> String {
+    let sequence = binding.sequence.iter().map(|(trigger, mods)| (trigger, *mods));
+    let keys =
+        iter::once((&binding.trigger, binding.mods)).chain(sequence).map(|(trigger, mods)| {
+            match trigger {
+                BindingKey::Keycode { key, .. } => key_text(key, mods),
+                BindingKey::Scancode(_) => {
+                    key_text(&Key::Unidentified(NativeKey::Unidentified), mods)
+                },
+            }
+        });
+    keys.collect::<Vec<_>>().join(" ")
+}
+
+/// Description of a program with its arguments.
+fn program_text(program: &Program) -> String {
+    iter::once(program.program())
+        .chain(program.args().iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matching() {
+        assert_eq!(fuzzy_score("", "Copy"), Some(0));
+        assert_eq!(fuzzy_score("copy", "Copy"), Some(0));
+        assert_eq!(fuzzy_score("cp", "Copy"), Some(1));
+        assert_eq!(fuzzy_score("inc font", "IncreaseFontSize"), Some(5));
+        assert_eq!(fuzzy_score("yc", "Copy"), None);
+    }
+
+    #[test]
+    fn filter_actions() {
+        let mut palette = CommandPalette::new(&UiConfig::default(), BindingMode::empty(), None);
+        assert!(palette.entries.iter().all(|entry| entry.action != Action::CommandPalette));
+
+        for c in "clearhist".chars() {
+            palette.input(c);
+        }
+        assert_eq!(palette.selected_action(), Some(&Action::ClearHistory));
+
+        let lines = palette.lines(20, 5);
+        assert_eq!(lines[0], ": clearhist");
+        assert_eq!(lines[2], "> ClearHistory      ");
+    }
+
+    #[test]
+    fn clipboard_history() {
+        let mut palette = CommandPalette::clipboard_history(["ls\n", "cargo test"].into_iter());
+        assert_eq!(palette.lines(20, 5)[2], "> ls⏎               ");
+
+        palette.input('c');
+        assert_eq!(palette.selected_action(), Some(&Action::PasteText("cargo test".into())));
+    }
+}
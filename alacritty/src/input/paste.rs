@@ -0,0 +1,126 @@
+//! Confirmation of potentially dangerous pastes.
+
+use crate::config::terminal::Paste;
+use crate::input::truncate;
+
+/// Maximum number of pasted lines shown in the confirmation preview.
+const PREVIEW_LINES: usize = 5;
+
+/// Reason for requiring paste confirmation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PasteWarning {
+    /// Line breaks which are sent as Enter key presses.
+    LineBreaks,
+    /// Control characters which could be interpreted as key presses or escapes.
+    ControlCharacters,
+    /// Paste exceeding the configured size.
+    Size,
+}
+
+/// Paste waiting for confirmation by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteConfirmation {
+    pub text: String,
+    pub bracketed: bool,
+    warning: PasteWarning,
+}
+
+impl PasteConfirmation {
+    /// Check whether a paste requires confirmation.
+    ///
+    /// The `bracketed_mode` indicates whether the application has enabled bracketed paste, in
+    /// which case line breaks are not sent as key presses.
+    pub fn new(config: &Paste, text: &str, bracketed: bool, bracketed_mode: bool) -> Option<Self> {
+        let warning = if config.max_size != 0 && text.len() > config.max_size {
+            PasteWarning::Size
+        } else if !config.confirm {
+            return None;
+        } else if text.chars().any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t')) {
+            PasteWarning::ControlCharacters
+        } else if !(bracketed && bracketed_mode) && text.contains(['\n', '\r']) {
+            PasteWarning::LineBreaks
+        } else {
+            return None;
+        };
+
+        Some(Self { text: text.into(), bracketed, warning })
+    }
+
+    /// Text of the confirmation dialog, truncated to the number of columns.
+    pub fn lines(&self, columns: usize) -> Vec<String> {
+        let reason = match self.warning {
+            PasteWarning::LineBreaks => "Line breaks will be sent as Enter",
+            PasteWarning::ControlCharacters => "Text contains control characters",
+            PasteWarning::Size => "Text exceeds the paste size limit",
+        };
+
+        let text_lines: Vec<&str> = self.text.lines().collect();
+        let mut lines = vec![
+            format!("Paste {} lines ({} bytes)?", text_lines.len(), self.text.len()),
+            reason.into(),
+            String::new(),
+        ];
+
+        for line in text_lines.iter().take(PREVIEW_LINES) {
+            lines.push(format!("> {}", escape_control(line)));
+        }
+        if text_lines.len() > PREVIEW_LINES {
+            lines.push(format!("  ... {} more lines", text_lines.len() - PREVIEW_LINES));
+        }
+
+        lines.push(String::new());
+        lines.push("[Enter] Paste  [Escape] Cancel".into());
+
+        lines.iter().map(|line| truncate(line, columns)).collect()
+    }
+}
+
+/// Replace control characters with their caret notation.
+fn escape_control(line: &str) -> String {
+    let mut escaped = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '\t' => escaped.push(' '),
+            '\x7f' => escaped.push_str("^?"),
+            c if (c as u32) < 0x20 => {
+                escaped.push('^');
+                escaped.push((c as u8 + 0x40) as char);
+            },
+            c if c.is_control() => escaped.push('�'),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paste_warnings() {
+        let config = Paste { max_size: 16, ..Default::default() };
+        let warning = |text: &str, bracketed_mode: bool| {
+            PasteConfirmation::new(&config, text, true, bracketed_mode).map(|paste| paste.warning)
+        };
+
+        assert_eq!(warning("ls", false), None);
+        assert_eq!(warning("rm -rf ~\n", false), Some(PasteWarning::LineBreaks));
+        assert_eq!(warning("rm -rf ~\n", true), None);
+        assert_eq!(warning("a\x1b[201~b", true), Some(PasteWarning::ControlCharacters));
+        assert_eq!(warning("0123456789abcdefg", true), Some(PasteWarning::Size));
+
+        let config = Paste { confirm: false, ..Default::default() };
+        assert_eq!(PasteConfirmation::new(&config, "rm -rf ~\n", true, false), None);
+    }
+
+    #[test]
+    fn preview_lines() {
+        let paste = PasteConfirmation::new(&Default::default(), "a\tb\x03\n1234567", true, false);
+        let lines = paste.unwrap().lines(8);
+
+        assert_eq!(lines[0], "Paste 2…");
+        assert_eq!(lines[3], "> a b^C");
+        assert_eq!(lines[4], "> 12345…");
+    }
+}
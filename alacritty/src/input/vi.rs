@@ -0,0 +1,829 @@
+//! Vi mode command composition.
+//!
+//! Individual vi motions and actions are dispatched through key bindings, while this module
+//! handles the parts of vi's normal mode grammar that span multiple keys: numeric counts, the
+//! yank operator combined with motions or text objects, and marks.
+
+use std::cmp::{max, min};
+use std::collections::{HashMap, VecDeque};
+use std::mem;
+
+use log::debug;
+use winit::keyboard::ModifiersState;
+
+use alacritty_terminal::event::EventListener;
+use alacritty_terminal::grid::{BidirectionalIterator, Dimensions};
+use alacritty_terminal::index::{Boundary, Column, Direction, Line, Point, Side};
+use alacritty_terminal::selection::{Selection, SelectionType};
+use alacritty_terminal::term::cell::Flags;
+use alacritty_terminal::term::{ClipboardType, Term};
+use alacritty_terminal::vi_mode::ViMotion;
+
+use crate::input::{ActionContext, Processor};
+
+/// Upper limit for vi command counts.
+const MAX_COUNT: usize = 99_999;
+
+/// Maximum number of entries in the jump list.
+const MAX_JUMPS: usize = 100;
+
+/// State of multi-key vi commands.
+#[derive(Default, Debug)]
+pub struct ViCommandState {
+    /// Count typed before the next command.
+    count: Option<usize>,
+
+    /// Operator waiting for a motion or text object.
+    operator: Option<PendingOperator>,
+
+    /// Whether the operator was started by the key which is currently processed.
+    operator_started: bool,
+
+    /// Command waiting for a character argument.
+    char_pending: Option<PendingChar>,
+
+    /// Marks set using `m{a-z}`.
+    marks: HashMap<char, ViMark>,
+
+    /// Positions before large vi cursor jumps, oldest first.
+    jumps: VecDeque<ViMark>,
+
+    /// Current position in the jump list, equal to its length when no jump was undone.
+    jump_index: usize,
+
+    /// Line number typed after `:`.
+    line_input: Option<String>,
+}
+
+impl ViCommandState {
+    /// Count for the next command, defaulting to one.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count.unwrap_or(1)
+    }
+
+    /// Count for the next command, if one was typed.
+    #[inline]
+    pub fn explicit_count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// Count for the next command, multiplied by the count of its operator like in `2y3j`.
+    fn operator_count(&self, operator: Option<PendingOperator>) -> usize {
+        // Both counts are limited, but their product must be limited as well.
+        let count = self.count() * operator.map_or(1, |operator| operator.count);
+        min(count, MAX_COUNT)
+    }
+
+    /// Line number prompt text, if it is active.
+    #[inline]
+    pub fn line_input(&self) -> Option<&str> {
+        self.line_input.as_deref()
+    }
+
+    /// Start the `:` line number prompt.
+    #[inline]
+    pub fn start_line_input(&mut self) {
+        self.line_input = Some(String::new());
+    }
+
+    /// Wait for the character argument of a command.
+    #[inline]
+    pub fn start_char_pending(&mut self, pending: PendingChar) {
+        self.char_pending = Some(pending);
+    }
+
+    /// Finish processing of a key which wasn't captured as part of a vi command.
+    ///
+    /// This drops the pending count and any operator which wasn't started by this key.
+    pub fn finish_key(&mut self) {
+        self.count = None;
+
+        if !mem::take(&mut self.operator_started) {
+            self.operator = None;
+        }
+    }
+
+    /// Clear all pending command input.
+    pub fn reset(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.operator_started = false;
+        self.char_pending = None;
+        self.line_input = None;
+    }
+
+    /// Add a position to the jump list.
+    ///
+    /// This drops all jumps which were undone and any older entry for the same line.
+    pub fn push_jump(&mut self, mark: ViMark) {
+        self.jumps.truncate(self.jump_index);
+        self.jumps.retain(|jump| jump.line != mark.line);
+        self.jumps.push_back(mark);
+
+        if self.jumps.len() > MAX_JUMPS {
+            self.jumps.pop_front();
+        }
+
+        self.jump_index = self.jumps.len();
+    }
+
+    /// Move backward in the jump list.
+    ///
+    /// The `current` position is recorded, so it's possible to return to it.
+    fn jump_backward(&mut self, current: ViMark) -> Option<ViMark> {
+        if self.jump_index == self.jumps.len() {
+            self.push_jump(current);
+            self.jump_index -= 1;
+        }
+
+        self.jump_index = self.jump_index.checked_sub(1)?;
+        self.jumps.get(self.jump_index).copied()
+    }
+
+    /// Move forward in the jump list.
+    fn jump_forward(&mut self) -> Option<ViMark> {
+        let mark = self.jumps.get(self.jump_index + 1).copied()?;
+        self.jump_index += 1;
+        Some(mark)
+    }
+
+    /// Start the yank operator, consuming the pending count.
+    fn start_operator(&mut self) {
+        let count = self.count.take().unwrap_or(1);
+        self.operator = Some(PendingOperator { count });
+        self.operator_started = true;
+    }
+
+    /// Append a digit to the pending count.
+    fn push_digit(&mut self, digit: u32) {
+        let count = self.count.unwrap_or(0) * 10 + digit as usize;
+        self.count = Some(min(count, MAX_COUNT));
+    }
+}
+
+/// Operator waiting for the range it should be applied to.
+///
+/// Since the terminal content can't be modified, yanking is the only supported operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct PendingOperator {
+    /// Count typed before the operator.
+    count: usize,
+}
+
+/// Vi commands which take a character as argument.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PendingChar {
+    /// Set a mark at the vi cursor.
+    SetMark,
+    /// Jump to a mark, `linewise` jumps to the first occupied cell of the mark's line.
+    JumpToMark { linewise: bool },
+    /// Text object for the pending operator, `around` includes delimiters and whitespace.
+    TextObject { around: bool },
+}
+
+/// Position attached to the terminal content, instead of the viewport.
+///
+/// The position stays valid while new output is added, until its line is rotated out of the
+/// scrollback history.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ViMark {
+    line: u64,
+    column: Column,
+}
+
+impl ViMark {
+    pub fn new<T>(term: &Term<T>, point: Point) -> Self {
+        let line = term.grid().scrolled_lines().wrapping_add(point.line.0 as i64 as u64);
+        Self { line, column: point.column }
+    }
+
+    /// Current position of the mark, if it's still part of the grid.
+    pub fn point<T>(&self, term: &Term<T>) -> Option<Point> {
+        let line = self.line.wrapping_sub(term.grid().scrolled_lines()) as i64;
+        if line < i64::from(term.topmost_line().0) || line > i64::from(term.bottommost_line().0) {
+            return None;
+        }
+
+        Some(Point::new(Line(line as i32), min(self.column, term.last_column())))
+    }
+}
+
+impl<T: EventListener, A: ActionContext<T>> Processor<T, A> {
+    /// Capture input which is part of a multi-key vi command.
+    ///
+    /// Returns `true` if the input was consumed and should not trigger any bindings.
+    pub(super) fn vi_command_input(&mut self, text: &str, mods: ModifiersState) -> bool {
+        let mut chars = text.chars();
+        let c = match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => None,
+        };
+
+        if self.ctx.vi_command_state().line_input.is_some() {
+            self.line_number_input(text);
+            return true;
+        }
+
+        if let Some(pending) = self.ctx.vi_command_state().char_pending.take() {
+            match c {
+                Some(c) => self.pending_char_input(pending, c),
+                // Keep waiting when there's no text, like for modifier presses.
+                None if text.is_empty() => self.ctx.vi_command_state().char_pending = Some(pending),
+                None => self.ctx.vi_command_state().reset(),
+            }
+            return true;
+        }
+
+        if mods.intersects(ModifiersState::CONTROL | ModifiersState::ALT | ModifiersState::SUPER) {
+            return false;
+        }
+
+        let state = self.ctx.vi_command_state();
+        match c {
+            // A leading zero is the `First` motion, not a count.
+            Some(c @ '0'..='9') if c != '0' || state.count.is_some() => {
+                state.push_digit(c.to_digit(10).unwrap());
+                true
+            },
+            Some(c @ ('i' | 'a')) if state.operator.is_some() => {
+                state.char_pending = Some(PendingChar::TextObject { around: c == 'a' });
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Handle the character argument of a pending vi command.
+    fn pending_char_input(&mut self, pending: PendingChar, c: char) {
+        match pending {
+            PendingChar::SetMark if c.is_ascii_lowercase() => {
+                let point = self.ctx.terminal().vi_mode_cursor.point;
+                let mark = ViMark::new(self.ctx.terminal(), point);
+                self.ctx.vi_command_state().marks.insert(c, mark);
+            },
+            PendingChar::JumpToMark { linewise } => {
+                let mark = self.ctx.vi_command_state().marks.get(&c).copied();
+                match mark.and_then(|mark| mark.point(self.ctx.terminal())) {
+                    Some(mut point) => {
+                        if linewise {
+                            point = first_occupied_in_line(self.ctx.terminal(), point.line);
+                        }
+
+                        record_jump(&mut self.ctx);
+                        self.ctx.on_typing_start();
+                        self.ctx.terminal_mut().vi_goto_point(point);
+                        self.ctx.mark_dirty();
+                    },
+                    None => debug!("Ignoring jump to unset mark {c:?}"),
+                }
+            },
+            PendingChar::TextObject { around } => {
+                let operator = self.ctx.vi_command_state().operator.take();
+                if operator.is_some() {
+                    yank_text_object(&mut self.ctx, c, around);
+                }
+            },
+            PendingChar::SetMark => debug!("Ignoring invalid mark {c:?}"),
+        }
+
+        self.ctx.vi_command_state().reset();
+    }
+
+    /// Handle input for the `:` line number prompt.
+    fn line_number_input(&mut self, text: &str) {
+        for c in text.chars() {
+            let state = self.ctx.vi_command_state();
+            let input = match &mut state.line_input {
+                Some(input) => input,
+                None => break,
+            };
+
+            match c {
+                '0'..='9' => input.push(c),
+                // Backspace on an empty prompt cancels it, like in vi.
+                '\x08' | '\x7f' if input.is_empty() => state.line_input = None,
+                '\x08' | '\x7f' => {
+                    input.pop();
+                },
+                '\r' | '\n' => match state.line_input.take().and_then(|input| input.parse().ok()) {
+                    Some(line) => goto_line(&mut self.ctx, line),
+                    None => debug!("Ignoring empty line number"),
+                },
+                '\x1b' => state.line_input = None,
+                _ => (),
+            }
+        }
+
+        self.ctx.mark_dirty();
+    }
+}
+
+/// Store the vi cursor position in the jump list.
+pub fn record_jump<T: EventListener, A: ActionContext<T>>(ctx: &mut A) {
+    let term = ctx.terminal();
+    let mark = ViMark::new(term, term.vi_mode_cursor.point);
+    ctx.vi_command_state().push_jump(mark);
+}
+
+/// Move through the jump list, skipping positions which are no longer in the scrollback.
+pub fn jump<T: EventListener, A: ActionContext<T>>(ctx: &mut A, direction: Direction) {
+    let term = ctx.terminal();
+    let current = ViMark::new(term, term.vi_mode_cursor.point);
+
+    let mut remaining = ctx.vi_command_state().count();
+    let mut target = None;
+    while remaining > 0 {
+        let state = ctx.vi_command_state();
+        let mark = match direction {
+            Direction::Left => state.jump_backward(current),
+            Direction::Right => state.jump_forward(),
+        };
+
+        let mark = match mark {
+            Some(mark) => mark,
+            None => break,
+        };
+
+        if let Some(point) = mark.point(ctx.terminal()) {
+            target = Some(point);
+            remaining -= 1;
+        }
+    }
+
+    if let Some(point) = target {
+        ctx.on_typing_start();
+        ctx.terminal_mut().vi_goto_point(point);
+        ctx.mark_dirty();
+    }
+}
+
+/// Jump to a line, counting from one at the top of the scrollback history.
+pub fn goto_line<T: EventListener, A: ActionContext<T>>(ctx: &mut A, line: usize) {
+    record_jump(ctx);
+
+    let line = numbered_line(ctx.terminal_mut(), line);
+    let point = first_occupied_in_line(ctx.terminal(), line);
+
+    ctx.on_typing_start();
+    ctx.terminal_mut().vi_goto_point(point);
+    ctx.mark_dirty();
+}
+
+/// Grid line of a line number, restoring compressed lines when necessary.
+///
+/// Numbers start at the oldest compressed line, so they don't change when compressed lines are
+/// restored or compressed again.
+fn numbered_line<T>(term: &mut Term<T>, number: usize) -> Line {
+    let offset = number.saturating_sub(1);
+
+    let compressed_lines = term.grid().compressed_lines();
+    if let Some(missing) = compressed_lines.checked_sub(offset).filter(|&missing| missing > 0) {
+        term.grid_mut().thaw_history(missing);
+    }
+
+    let offset = offset.saturating_sub(term.grid().compressed_lines());
+    term.topmost_line() + min(offset, term.total_lines() - 1)
+}
+
+/// Handle the vi yank action.
+///
+/// With an active selection it is copied, otherwise the yank operator is started. Repeating the
+/// operator yanks whole lines, like `yy`.
+pub fn yank<T: EventListener, A: ActionContext<T>>(ctx: &mut A) {
+    if !ctx.selection_is_empty() {
+        ctx.copy_selection(ClipboardType::Clipboard);
+        ctx.clear_selection();
+        return;
+    }
+
+    let state = ctx.vi_command_state();
+    let operator = match state.operator.take() {
+        Some(operator) => operator,
+        None => {
+            state.start_operator();
+            return;
+        },
+    };
+
+    let count = state.operator_count(Some(operator));
+    let term = ctx.terminal();
+    let start = term.vi_mode_cursor.point;
+    let end_line = min(start.line + (count - 1), term.bottommost_line());
+    let end = Point::new(end_line, term.last_column());
+    yank_range(ctx, SelectionType::Lines, start, end);
+}
+
+/// Perform a vi motion, applying the pending count and operator.
+pub fn motion<T: EventListener, A: ActionContext<T>>(ctx: &mut A, motion: ViMotion) {
+    let operator = ctx.vi_command_state().operator.take();
+    let count = ctx.vi_command_state().operator_count(operator);
+
+    // Remember the origin of large motions, so it's possible to jump back.
+    if operator.is_none() && is_jump(motion) {
+        record_jump(ctx);
+    }
+
+    let origin = ctx.terminal().vi_mode_cursor.point;
+    for _ in 0..count {
+        ctx.terminal_mut().vi_motion(motion);
+    }
+
+    if operator.is_none() {
+        return;
+    }
+
+    let term = ctx.terminal();
+    let target = term.vi_mode_cursor.point;
+    let start = min(origin, target);
+    let mut end = max(origin, target);
+
+    if is_linewise(motion) {
+        yank_range(ctx, SelectionType::Lines, start, end);
+    } else {
+        if !is_inclusive(motion) {
+            // Like vi, exclusive motions ending in the first column stop at the previous line.
+            if end.column == 0 && end.line > start.line {
+                end = Point::new(end.line - 1, term.last_column());
+            } else if end > start {
+                end = end.sub(term, Boundary::Grid, 1);
+            } else {
+                return;
+            }
+        }
+
+        yank_range(ctx, SelectionType::Simple, start, end);
+    }
+
+    // Vi leaves the cursor at the start of the yanked text.
+    ctx.terminal_mut().vi_goto_point(start);
+}
+
+/// Copy a text object around the vi cursor.
+fn yank_text_object<T: EventListener, A: ActionContext<T>>(
+    ctx: &mut A,
+    object: char,
+    around: bool,
+) {
+    let point = ctx.terminal().vi_mode_cursor.point;
+    match text_object(ctx.terminal(), point, object, around) {
+        Some((start, end)) => {
+            yank_range(ctx, SelectionType::Simple, start, end);
+            ctx.terminal_mut().vi_goto_point(start);
+            ctx.mark_dirty();
+        },
+        None => debug!("No text object {object:?} at {point:?}"),
+    }
+}
+
+/// Copy the text between two points to the clipboard, without touching the active selection.
+fn yank_range<T: EventListener, A: ActionContext<T>>(
+    ctx: &mut A,
+    ty: SelectionType,
+    start: Point,
+    end: Point,
+) {
+    let mut selection = Selection::new(ty, start, Side::Left);
+    selection.update(end, Side::Right);
+
+    let previous = ctx.terminal_mut().selection.replace(selection);
+    ctx.copy_selection(ClipboardType::Clipboard);
+    ctx.terminal_mut().selection = previous;
+}
+
+/// Motions which operate on entire lines.
+fn is_linewise(motion: ViMotion) -> bool {
+    matches!(
+        motion,
+        ViMotion::Up | ViMotion::Down | ViMotion::High | ViMotion::Middle | ViMotion::Low
+    )
+}
+
+/// Motions which are added to the jump list.
+fn is_jump(motion: ViMotion) -> bool {
+    matches!(
+        motion,
+        ViMotion::High
+            | ViMotion::Middle
+            | ViMotion::Low
+            | ViMotion::Bracket
+            | ViMotion::ParagraphUp
+            | ViMotion::ParagraphDown
+    )
+}
+
+/// Motions which include the target cell in their range.
+fn is_inclusive(motion: ViMotion) -> bool {
+    matches!(
+        motion,
+        ViMotion::Last
+            | ViMotion::SemanticRightEnd
+            | ViMotion::WordRightEnd
+            | ViMotion::SemanticLeftEnd
+            | ViMotion::WordLeftEnd
+            | ViMotion::Bracket
+    )
+}
+
+/// Find the bounds of a text object at `point`.
+pub fn text_object<T>(
+    term: &Term<T>,
+    point: Point,
+    object: char,
+    around: bool,
+) -> Option<(Point, Point)> {
+    let point = term.expand_wide(point, Direction::Left);
+
+    match object {
+        'w' => word_object(term, point, around, false),
+        'W' => word_object(term, point, around, true),
+        '"' | '\'' | '`' => quote_object(term, point, object, around),
+        '(' | ')' | 'b' => bracket_object(term, point, ('(', ')'), around),
+        '{' | '}' | 'B' => bracket_object(term, point, ('{', '}'), around),
+        '[' | ']' => bracket_object(term, point, ('[', ']'), around),
+        '<' | '>' => bracket_object(term, point, ('<', '>'), around),
+        _ => None,
+    }
+}
+
+/// Word text object, `big` words are only separated by whitespace.
+fn word_object<T>(term: &Term<T>, point: Point, around: bool, big: bool) -> Option<(Point, Point)> {
+    let class = |c: char| {
+        if c == ' ' || c == '\t' {
+            0
+        } else if big || !term.semantic_escape_chars().contains(c) {
+            1
+        } else {
+            2
+        }
+    };
+
+    let point_class = class(term.grid()[point].c);
+    let mut start = expand_while(term, point, Direction::Left, |c| class(c) == point_class);
+    let mut end = expand_while(term, point, Direction::Right, |c| class(c) == point_class);
+
+    // Include trailing whitespace, or leading whitespace if there is none.
+    if around && point_class != 0 {
+        let trailing = expand_while(term, end, Direction::Right, |c| class(c) == 0);
+        if trailing == end {
+            start = expand_while(term, start, Direction::Left, |c| class(c) == 0);
+        } else {
+            end = trailing;
+        }
+    }
+
+    Some((start, end))
+}
+
+/// Quoted string text object within the current line.
+fn quote_object<T>(
+    term: &Term<T>,
+    point: Point,
+    quote: char,
+    around: bool,
+) -> Option<(Point, Point)> {
+    let needle = quote.encode_utf8(&mut [0; 4]).to_owned();
+
+    let (open, close) = if term.grid()[point].c == quote {
+        // Count the preceding quotes to check if the cursor is on the opening quote.
+        let line_start = term.line_search_left(point);
+        let preceding = term
+            .grid()
+            .iter_from(line_start.sub(term, Boundary::Grid, 1))
+            .take_while(|cell| cell.point < point)
+            .filter(|cell| cell.c == quote)
+            .count();
+
+        if preceding % 2 == 0 {
+            (point, term.inline_search_right(point, &needle).ok()?)
+        } else {
+            (term.inline_search_left(point, &needle).ok()?, point)
+        }
+    } else {
+        let open = term.inline_search_left(point, &needle).ok()?;
+        (open, term.inline_search_right(point, &needle).ok()?)
+    };
+
+    delimited_bounds(term, open, close, around)
+}
+
+/// Bracket pair text object, enclosing the point.
+fn bracket_object<T>(
+    term: &Term<T>,
+    point: Point,
+    (open_char, close_char): (char, char),
+    around: bool,
+) -> Option<(Point, Point)> {
+    let c = term.grid()[point].c;
+    let open = if c == open_char {
+        point
+    } else if c == close_char {
+        term.bracket_search(point)?
+    } else {
+        // Find the closest unmatched opening bracket to the left.
+        let mut iter = term.grid().iter_from(point);
+        let mut depth = 0;
+        loop {
+            let cell = iter.prev()?;
+            if cell.c == close_char {
+                depth += 1;
+            } else if cell.c == open_char {
+                if depth == 0 {
+                    break cell.point;
+                }
+                depth -= 1;
+            }
+        }
+    };
+
+    let close = term.bracket_search(open)?;
+
+    delimited_bounds(term, open, close, around)
+}
+
+/// Bounds of a text object enclosed by the `open` and `close` delimiters.
+fn delimited_bounds<T>(
+    term: &Term<T>,
+    open: Point,
+    close: Point,
+    around: bool,
+) -> Option<(Point, Point)> {
+    if around {
+        return Some((open, close));
+    }
+
+    let start = open.add(term, Boundary::Grid, 1);
+    let end = close.sub(term, Boundary::Grid, 1);
+    (start <= end).then_some((start, end))
+}
+
+/// Move from `point` in `direction` as long as the characters match the `predicate`.
+///
+/// The search does not leave the logical line, but crosses linewraps.
+fn expand_while<T, F>(term: &Term<T>, mut point: Point, direction: Direction, predicate: F) -> Point
+where
+    F: Fn(char) -> bool,
+{
+    let last_column = term.last_column();
+    let wide_spacer = Flags::WIDE_CHAR_SPACER | Flags::LEADING_WIDE_CHAR_SPACER;
+
+    let mut probe = point;
+    loop {
+        probe = match direction {
+            Direction::Right if probe.column == last_column => {
+                if probe.line >= term.bottommost_line()
+                    || !term.grid()[probe].flags.contains(Flags::WRAPLINE)
+                {
+                    break;
+                }
+                Point::new(probe.line + 1, Column(0))
+            },
+            Direction::Right => Point::new(probe.line, probe.column + 1),
+            Direction::Left if probe.column == 0 => {
+                let wrap_point = Point::new(probe.line - 1, last_column);
+                if probe.line <= term.topmost_line()
+                    || !term.grid()[wrap_point].flags.contains(Flags::WRAPLINE)
+                {
+                    break;
+                }
+                wrap_point
+            },
+            Direction::Left => Point::new(probe.line, probe.column - 1),
+        };
+
+        let cell = &term.grid()[probe];
+
+        // Spacers belong to the wide character on their left.
+        if cell.flags.intersects(wide_spacer) {
+            if direction == Direction::Right && cell.flags.contains(Flags::WIDE_CHAR_SPACER) {
+                point = probe;
+            }
+            continue;
+        }
+
+        if !predicate(cell.c) {
+            break;
+        }
+
+        point = probe;
+    }
+
+    point
+}
+
+/// First occupied cell in a line, or the first column if the line is empty.
+fn first_occupied_in_line<T>(term: &Term<T>, line: Line) -> Point {
+    let row = &term.grid()[line];
+    let column = (0..term.columns()).map(Column).find(|column| row[*column].c != ' ');
+    Point::new(line, column.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alacritty_terminal::event::VoidListener;
+    use alacritty_terminal::term::Config;
+    use alacritty_terminal::term::test::{TermSize, mock_term};
+    use alacritty_terminal::vte::ansi::Handler;
+
+    fn object_text(content: &str, column: usize, object: char, around: bool) -> Option<String> {
+        let term = mock_term(content);
+        let point = Point::new(Line(0), Column(column));
+        text_object(&term, point, object, around)
+            .map(|(start, end)| term.bounds_to_string(start, end))
+    }
+
+    #[test]
+    fn word_objects() {
+        assert_eq!(object_text("foo bar,baz qux", 5, 'w', false).as_deref(), Some("bar"));
+        assert_eq!(object_text("foo bar,baz qux", 5, 'w', true).as_deref(), Some(" bar"));
+        assert_eq!(object_text("foo bar baz", 5, 'w', true).as_deref(), Some("bar "));
+        assert_eq!(object_text("foo bar", 5, 'w', true).as_deref(), Some(" bar"));
+        assert_eq!(object_text("foo bar,baz qux", 5, 'W', false).as_deref(), Some("bar,baz"));
+    }
+
+    #[test]
+    fn quote_objects() {
+        let content = r#"a "b c" d "e""#;
+        assert_eq!(object_text(content, 4, '"', false).as_deref(), Some("b c"));
+        assert_eq!(object_text(content, 4, '"', true).as_deref(), Some("\"b c\""));
+        assert_eq!(object_text(content, 2, '"', false).as_deref(), Some("b c"));
+        assert_eq!(object_text(content, 6, '"', false).as_deref(), Some("b c"));
+        assert_eq!(object_text(content, 0, '"', false), None);
+    }
+
+    #[test]
+    fn bracket_objects() {
+        let content = "f(a, (b), c) [x]";
+        assert_eq!(object_text(content, 3, '(', false).as_deref(), Some("a, (b), c"));
+        assert_eq!(object_text(content, 6, 'b', false).as_deref(), Some("b"));
+        assert_eq!(object_text(content, 9, ')', true).as_deref(), Some("(a, (b), c)"));
+        assert_eq!(object_text(content, 1, '(', true).as_deref(), Some("(a, (b), c)"));
+        assert_eq!(object_text(content, 14, ']', false).as_deref(), Some("x"));
+        assert_eq!(object_text(content, 14, '{', false), None);
+    }
+
+    #[test]
+    fn clamp_counts() {
+        let mut state = ViCommandState::default();
+        for _ in 0..10 {
+            state.push_digit(9);
+        }
+        assert_eq!(state.count(), MAX_COUNT);
+
+        let operator = PendingOperator { count: MAX_COUNT };
+        assert_eq!(state.operator_count(Some(operator)), MAX_COUNT);
+        assert_eq!(ViCommandState::default().operator_count(Some(operator)), MAX_COUNT);
+    }
+
+    #[test]
+    fn stable_line_numbers() {
+        let size = TermSize::new(5, 2);
+        let config =
+            Config { scrolling_history: 1, compressed_history: 1024, ..Default::default() };
+        let mut term = Term::new(config, &size, VoidListener);
+        for c in "a\r\nb\r\nc\r\nd\r\ne".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+        assert_eq!(term.grid().compressed_lines(), 2);
+
+        // Numbers count compressed lines, restoring the ones at or above the target.
+        assert_eq!(numbered_line(&mut term, 4), Line(0));
+        assert_eq!(term.grid().compressed_lines(), 2);
+        assert_eq!(numbered_line(&mut term, 2), Line(-2));
+        assert_eq!(term.grid()[Line(-2)][Column(0)].c, 'b');
+        assert_eq!(term.grid().compressed_lines(), 1);
+
+        // Restored lines keep their numbers.
+        assert_eq!(numbered_line(&mut term, 4), Line(0));
+        assert_eq!(numbered_line(&mut term, 1), Line(-3));
+        assert_eq!(numbered_line(&mut term, 9), Line(1));
+    }
+
+    #[test]
+    fn jump_list() {
+        let mark = |line| ViMark { line, column: Column(0) };
+        let mut state = ViCommandState::default();
+
+        state.push_jump(mark(1));
+        state.push_jump(mark(2));
+        state.push_jump(mark(1));
+
+        assert_eq!(state.jump_backward(mark(3)), Some(mark(1)));
+        assert_eq!(state.jump_backward(mark(1)), Some(mark(2)));
+        assert_eq!(state.jump_backward(mark(2)), None);
+        assert_eq!(state.jump_forward(), Some(mark(1)));
+        assert_eq!(state.jump_forward(), Some(mark(3)));
+        assert_eq!(state.jump_forward(), None);
+
+        // New jumps drop the undone entries.
+        state.jump_backward(mark(3));
+        state.push_jump(mark(1));
+        assert_eq!(state.jumps, [mark(2), mark(1)]);
+    }
+}
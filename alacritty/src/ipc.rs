@@ -83,6 +83,21 @@ pub fn spawn_ipc_socket(
                     let event = Event::new(EventType::IpcGetConfig(Arc::new(stream)), window_id);
                     let _ = event_proxy.send_event(event);
                 },
+                SocketMessage::Signal(ipc_signal) => {
+                    // Only send the signal to all windows when explicitly requested.
+                    let window_id = match ipc_signal.window_id {
+                        -1 => None,
+                        id => match u64::try_from(id) {
+                            Ok(id) => Some(WindowId::from(id)),
+                            Err(_) => {
+                                warn!("Invalid window ID for signal: {id}");
+                                continue;
+                            },
+                        },
+                    };
+                    let event = Event::new(EventType::Signal(ipc_signal.signal), window_id);
+                    let _ = event_proxy.send_event(event);
+                },
             }
         }
     });
@@ -338,7 +338,7 @@ fn ensure_click_handler_class() -> &'static AnyClass {
             // 通过事件代理请求创建新窗口；随后无条件显示所有窗口。
             if let Some(proxy) = EVENT_PROXY.get() {
                 let _ = proxy.send_event(Event::new(
-                    EventType::CreateWindow(WindowOptions::default()),
+                    EventType::CreateWindow(Box::default()),
                     None,
                 ));
                 let _ = proxy.send_event(Event::new(EventType::ShowAllWindows, None));
@@ -481,7 +481,7 @@ fn ensure_click_handler_class() -> &'static AnyClass {
                 if let Some(proxy) = EVENT_PROXY.get() {
                     let mut opts = WindowOptions::default();
                     opts.terminal_options.working_directory = Some(PathBuf::from(path));
-                    let _ = proxy.send_event(Event::new(EventType::CreateWindow(opts), None));
+                    let _ = proxy.send_event(Event::new(EventType::CreateWindow(Box::new(opts)), None));
                     let _ = proxy.send_event(Event::new(EventType::ShowAllWindows, None));
                 }
             }
@@ -43,8 +43,10 @@ mod message_bar;
 mod migrate;
 #[cfg(windows)]
 mod panic;
+mod pty_backend;
 mod renderer;
 mod scheduler;
+mod scrollback_log;
 mod string;
 mod path_util;
 mod window_context;
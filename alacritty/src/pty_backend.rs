@@ -0,0 +1,282 @@
+//! Sources of the terminal I/O of windows.
+
+use std::collections::HashMap;
+use std::io;
+#[cfg(all(unix, debug_assertions))]
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::io::{FromRawFd, OwnedFd};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::rc::Rc;
+#[cfg(unix)]
+use std::time::Duration;
+
+use winit::window::WindowId;
+
+use alacritty_terminal::event::WindowSize;
+#[cfg(all(unix, debug_assertions))]
+use alacritty_terminal::thread;
+use alacritty_terminal::tty::{self, Pty};
+
+use crate::cli::TerminalOptions;
+
+/// Name of the backend spawning the configured shell.
+pub const DEFAULT_BACKEND: &str = "shell";
+
+/// Maximum time to wait for a TCP connection to each resolved address.
+#[cfg(unix)]
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Information available to backends when creating the PTY of a window.
+pub struct PtyContext<'a> {
+    pub pty_config: &'a tty::Options,
+    pub terminal_options: &'a TerminalOptions,
+    pub window_size: WindowSize,
+    pub window_id: WindowId,
+}
+
+/// Source of the terminal I/O of a window.
+pub trait PtyBackend {
+    /// Create the PTY of a window.
+    ///
+    /// This is called again with the same context when the window's shell is respawned.
+    fn create(&self, context: &PtyContext<'_>) -> io::Result<Pty>;
+}
+
+/// Backend spawning the configured shell.
+struct ShellBackend;
+
+impl PtyBackend for ShellBackend {
+    fn create(&self, context: &PtyContext<'_>) -> io::Result<Pty> {
+        tty::new(context.pty_config, context.window_size, context.window_id.into())
+    }
+}
+
+/// Backend attaching to a file descriptor passed through the CLI.
+#[cfg(unix)]
+struct FdBackend;
+
+#[cfg(unix)]
+impl PtyBackend for FdBackend {
+    fn create(&self, context: &PtyContext<'_>) -> io::Result<Pty> {
+        let fd = context
+            .terminal_options
+            .attach_fd
+            .ok_or_else(|| io::Error::other("no file descriptor to attach to"))?;
+
+        // SAFETY: The file descriptor was checked to be open when parsing the CLI and it's only
+        // handed to the initial window, which can't be respawned.
+        tty::attach(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+}
+
+/// Backend using a serial device.
+#[cfg(unix)]
+struct SerialBackend;
+
+#[cfg(unix)]
+impl PtyBackend for SerialBackend {
+    fn create(&self, context: &PtyContext<'_>) -> io::Result<Pty> {
+        let options = context.terminal_options;
+        let device = options.serial.as_ref().ok_or_else(|| io::Error::other("no serial device"))?;
+        tty::serial(device, options.baud())
+    }
+}
+
+/// Backend connecting to a Unix socket path or TCP address speaking raw bytes.
+#[cfg(unix)]
+struct SocketBackend;
+
+#[cfg(unix)]
+impl PtyBackend for SocketBackend {
+    fn create(&self, context: &PtyContext<'_>) -> io::Result<Pty> {
+        let address = context
+            .terminal_options
+            .connect
+            .as_ref()
+            .ok_or_else(|| io::Error::other("no socket address"))?;
+
+        let fd: OwnedFd = if address.contains('/') || !address.contains(':') {
+            UnixStream::connect(address)?.into()
+        } else {
+            connect_tcp(address)?.into()
+        };
+
+        tty::attach(fd)
+    }
+}
+
+/// Connect to the first reachable address, without blocking the UI for longer than the timeout.
+#[cfg(unix)]
+fn connect_tcp(address: &str) -> io::Result<TcpStream> {
+    let mut error = None;
+    for socket_address in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket_address, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => error = Some(err),
+        }
+    }
+
+    Err(error.unwrap_or_else(|| io::Error::other(format!("no addresses for `{address}`"))))
+}
+
+/// Backend running an in-process source on one end of a socket pair.
+///
+/// Everything written to the terminal can be read from the source's stream, while everything
+/// written to the stream is shown by the terminal. This is only available in debug builds, for
+/// testing the UI against scripted output.
+#[cfg(all(unix, debug_assertions))]
+pub struct InProcessBackend<F> {
+    source: F,
+}
+
+#[cfg(all(unix, debug_assertions))]
+impl<F> InProcessBackend<F>
+where
+    F: Fn(UnixStream) + Clone + Send + 'static,
+{
+    pub fn new(source: F) -> Self {
+        Self { source }
+    }
+}
+
+#[cfg(all(unix, debug_assertions))]
+impl<F> PtyBackend for InProcessBackend<F>
+where
+    F: Fn(UnixStream) + Clone + Send + 'static,
+{
+    fn create(&self, _context: &PtyContext<'_>) -> io::Result<Pty> {
+        let (terminal, source) = UnixStream::pair()?;
+
+        let run_source = self.source.clone();
+        thread::spawn_named("PTY source", move || run_source(source));
+
+        tty::attach(terminal.into())
+    }
+}
+
+/// Loop input back to the terminal, for testing the UI without a shell.
+#[cfg(all(unix, debug_assertions))]
+fn echo(mut stream: UnixStream) {
+    let mut buf = [0; 1024];
+    while let Ok(len @ 1..) = stream.read(&mut buf) {
+        let output: Vec<u8> = buf[..len]
+            .iter()
+            .flat_map(
+                |byte| if *byte == b'\r' { b"\r\n".as_slice() } else { std::slice::from_ref(byte) },
+            )
+            .copied()
+            .collect();
+
+        if stream.write_all(&output).is_err() {
+            break;
+        }
+    }
+}
+
+/// Registry of the available PTY backends by name.
+pub struct PtyBackends {
+    backends: HashMap<String, Rc<dyn PtyBackend>>,
+}
+
+impl Default for PtyBackends {
+    fn default() -> Self {
+        let mut backends = Self { backends: HashMap::new() };
+        backends.register(DEFAULT_BACKEND, ShellBackend);
+
+        #[cfg(unix)]
+        {
+            backends.register("fd", FdBackend);
+            backends.register("serial", SerialBackend);
+            backends.register("socket", SocketBackend);
+        }
+
+        #[cfg(all(unix, debug_assertions))]
+        backends.register("echo", InProcessBackend::new(echo));
+
+        backends
+    }
+}
+
+impl PtyBackends {
+    /// Add a backend, replacing any backend with the same name.
+    pub fn register<B: PtyBackend + 'static>(&mut self, name: &str, backend: B) {
+        self.backends.insert(name.into(), Rc::new(backend));
+    }
+
+    /// Get the backend selected by the terminal options.
+    pub fn select(&self, options: &TerminalOptions) -> Result<Rc<dyn PtyBackend>, String> {
+        let name = options.backend_name();
+        self.backends.get(name).cloned().ok_or_else(|| format!("unknown PTY backend `{name}`"))
+    }
+}
+
+#[cfg(all(test, unix, debug_assertions))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_backends() {
+        let backends = PtyBackends::default();
+
+        let mut options = TerminalOptions::default();
+        assert!(backends.select(&options).is_ok());
+
+        options.backend = Some("missing".into());
+        assert!(backends.select(&options).is_err());
+
+        options.backend = Some("echo".into());
+        assert!(backends.select(&options).is_ok());
+    }
+
+    #[test]
+    fn tcp_connection() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        assert!(connect_tcp(&address).is_ok());
+
+        assert!(connect_tcp("127.0.0.1").is_err());
+    }
+
+    #[test]
+    fn in_process_backend() {
+        let mut backends = PtyBackends::default();
+        backends.register(
+            "script",
+            InProcessBackend::new(|mut stream: UnixStream| {
+                let _ = stream.write_all(b"scripted output");
+            }),
+        );
+
+        let mut options = TerminalOptions::default();
+        options.backend = Some("script".into());
+        let pty_config = tty::Options::default();
+        let context = PtyContext {
+            pty_config: &pty_config,
+            terminal_options: &options,
+            window_size: WindowSize { num_lines: 24, num_cols: 80, cell_width: 8, cell_height: 16 },
+            window_id: WindowId::from(0),
+        };
+        let pty = backends.select(&options).unwrap().create(&context).unwrap();
+        assert!(pty.child().is_none());
+
+        // The attached file descriptor is non-blocking, so wait for the source to write.
+        let mut output = Vec::new();
+        let mut file = pty.file();
+        for _ in 0..100 {
+            let mut buf = [0; 64];
+            match file.read(&mut buf) {
+                Ok(0) => break,
+                Ok(len) => output.extend_from_slice(&buf[..len]),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(10))
+                },
+                Err(err) => panic!("reading PTY failed: {err}"),
+            }
+        }
+        assert_eq!(output, b"scripted output");
+    }
+}
@@ -333,7 +333,7 @@ impl Renderer {
             gl::Viewport(
                 size.padding_x() as i32,
                 size.padding_y() as i32,
-                size.width() as i32 - 2 * size.padding_x() as i32,
+                (size.width() - size.padding_x() - size.padding_right()) as i32,
                 size.height() as i32 - 2 * size.padding_y() as i32,
             );
         }
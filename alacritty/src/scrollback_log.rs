@@ -0,0 +1,138 @@
+//! Log file for lines rotated out of the scrollback history.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+use log::{info, warn};
+use winit::window::WindowId;
+
+use crate::config::scrolling::Spill;
+
+/// Append-only log of a window's evicted scrollback, rotated by size.
+pub struct ScrollbackLog {
+    path: PathBuf,
+    file: Option<File>,
+    size: u64,
+    config: Spill,
+}
+
+impl ScrollbackLog {
+    /// Create the log for a window.
+    ///
+    /// The file is only created once the first line is written.
+    pub fn new(window_id: WindowId, config: Spill) -> Option<Self> {
+        let file_name = format!("{}-{}.log", process::id(), u64::from(window_id));
+        let path = match log_dir() {
+            Ok(dir) => dir.join(file_name),
+            Err(err) => {
+                warn!("Unable to create scrollback log directory: {err}");
+                return None;
+            },
+        };
+
+        Some(Self { path, file: None, size: 0, config })
+    }
+
+    /// Update the log's size limits.
+    pub fn update_config(&mut self, config: Spill) {
+        self.config = config;
+    }
+
+    /// Append text to the log, rotating it once it exceeds its maximum size.
+    pub fn write(&mut self, text: &str) {
+        if let Err(err) = self.write_fallible(text) {
+            warn!("Unable to write scrollback log {:?}: {err}", self.path);
+            self.file = None;
+        }
+    }
+
+    fn write_fallible(&mut self, text: &str) -> io::Result<()> {
+        if self.size > 0 && self.size + text.len() as u64 > self.config.max_size() {
+            self.file = None;
+            rotate(&self.path, self.config.files)?;
+        }
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+                self.size = file.metadata()?.len();
+                if self.size == 0 {
+                    info!("Writing scrollback log to {:?}", self.path);
+                }
+                self.file.insert(file)
+            },
+        };
+
+        file.write_all(text.as_bytes())?;
+        self.size += text.len() as u64;
+
+        Ok(())
+    }
+}
+
+/// Shift all rotated logs by one, keeping at most `files` of them.
+fn rotate(path: &Path, files: u8) -> io::Result<()> {
+    let rotated = |index: u8| {
+        let mut path = path.as_os_str().to_owned();
+        path.push(format!(".{index}"));
+        PathBuf::from(path)
+    };
+
+    if files == 0 {
+        return fs::remove_file(path);
+    }
+
+    // The oldest log is replaced by the rename.
+    for index in (1..files).rev() {
+        match fs::rename(rotated(index), rotated(index + 1)) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+            _ => (),
+        }
+    }
+
+    fs::rename(path, rotated(1))
+}
+
+/// Directory for the scrollback logs.
+#[cfg(not(windows))]
+fn log_dir() -> io::Result<PathBuf> {
+    let path = xdg::BaseDirectories::with_prefix("alacritty").place_state_file("scrollback")?;
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Directory for the scrollback logs.
+#[cfg(windows)]
+fn log_dir() -> io::Result<PathBuf> {
+    let path = dirs::data_local_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no local data directory"))?
+        .join("alacritty")
+        .join("scrollback");
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotate_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.log");
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).ok();
+
+        for content in ["a", "b", "c", "d"] {
+            fs::write(&path, content).unwrap();
+            rotate(&path, 2).unwrap();
+        }
+
+        assert_eq!(read("test.log"), None);
+        assert_eq!(read("test.log.1").as_deref(), Some("d"));
+        assert_eq!(read("test.log.2").as_deref(), Some("c"));
+        assert_eq!(read("test.log.3"), None);
+    }
+}
@@ -8,13 +8,13 @@ use std::mem;
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use glutin::config::Config as GlutinConfig;
 use glutin::display::GetGlDisplay;
 #[cfg(all(feature = "x11", not(any(target_os = "macos", windows))))]
 use glutin::platform::x11::X11GlConfigExt;
-use log::info;
+use log::{info, warn};
 use serde_json as json;
 use winit::event::{Event as WinitEvent, Modifiers, WindowEvent};
 use winit::event_loop::{ActiveEventLoop, EventLoopProxy};
@@ -29,22 +29,31 @@ use alacritty_terminal::sync::FairMutex;
 use alacritty_terminal::term::test::TermSize;
 use alacritty_terminal::term::{Term, TermMode};
 use alacritty_terminal::tty;
+use alacritty_terminal::vte::ansi::{Attr, Handler};
 
-use crate::cli::{ParsedOptions, WindowOptions};
+use crate::cli::{ParsedOptions, TerminalOptions, WindowOptions};
 use crate::clipboard::Clipboard;
+#[cfg(unix)]
+use crate::config::Signal;
 use crate::config::UiConfig;
+use crate::daemon::spawn_daemon;
+#[cfg(not(windows))]
+use crate::daemon::{foreground_process_id, foreground_process_path, kill_process_groups};
 use crate::display::Display;
-use crate::display::window::Window;
+use crate::display::title;
+use crate::display::window::{MonitorAlert, Window};
 use crate::event::{
-    ActionContext, Event, EventProxy, InlineSearchState, Mouse, SearchState, TouchPurpose,
+    ActionContext, Event, EventProxy, EventType, InlineSearchState, Mouse, SearchState,
+    TouchPurpose,
 };
+use crate::input::vi::ViCommandState;
 #[cfg(unix)]
 use crate::logging::LOG_TARGET_IPC_CONFIG;
 use crate::message_bar::MessageBuffer;
-use crate::scheduler::Scheduler;
+use crate::pty_backend::{PtyBackend, PtyBackends, PtyContext};
+use crate::scheduler::{Scheduler, TimerId, Topic};
+use crate::scrollback_log::ScrollbackLog;
 use crate::{input, renderer};
-#[cfg(not(windows))]
-use crate::daemon::foreground_process_path;
 
 /// Event context for one individual Alacritty window.
 pub struct WindowContext {
@@ -57,8 +66,15 @@ pub struct WindowContext {
     prev_bell_cmd: Option<Instant>,
     modifiers: Modifiers,
     inline_search_state: InlineSearchState,
+    vi_command_state: ViCommandState,
+    scrollback_log: Option<ScrollbackLog>,
     search_state: SearchState,
     notifier: Notifier,
+    event_proxy: EventProxy,
+    pty_config: tty::Options,
+    terminal_options: TerminalOptions,
+    pty_backend: Rc<dyn PtyBackend>,
+    last_output: Option<Instant>,
     mouse: Mouse,
     touch: TouchPurpose,
     occluded: bool,
@@ -80,6 +96,7 @@ impl WindowContext {
         proxy: EventLoopProxy<Event>,
         config: Rc<UiConfig>,
         mut options: WindowOptions,
+        pty_backends: &PtyBackends,
     ) -> Result<Self, Box<dyn Error>> {
         let raw_display_handle = event_loop.display_handle().unwrap().as_raw();
 
@@ -119,7 +136,7 @@ impl WindowContext {
 
         let display = Display::new(window, gl_context, &config, false)?;
 
-        Self::new(display, config, options, proxy)
+        Self::new(display, config, options, proxy, pty_backends)
     }
 
     /// Create additional context with the graphics platform other windows are using.
@@ -130,6 +147,7 @@ impl WindowContext {
         config: Rc<UiConfig>,
         mut options: WindowOptions,
         config_overrides: ParsedOptions,
+        pty_backends: &PtyBackends,
     ) -> Result<Self, Box<dyn Error>> {
         let gl_display = gl_config.display();
 
@@ -159,7 +177,7 @@ impl WindowContext {
 
         let display = Display::new(window, gl_context, &config, tabbed)?;
 
-        let mut window_context = Self::new(display, config, options, proxy)?;
+        let mut window_context = Self::new(display, config, options, proxy, pty_backends)?;
 
         // 统一通过事件处理器在 macOS 上前置显示，避免与 AppKit 行为冲突。
 
@@ -177,7 +195,9 @@ impl WindowContext {
         config: Rc<UiConfig>,
         options: WindowOptions,
         proxy: EventLoopProxy<Event>,
+        pty_backends: &PtyBackends,
     ) -> Result<Self, Box<dyn Error>> {
+        let pty_backend = pty_backends.select(&options.terminal_options)?;
         let mut pty_config = config.pty_config();
         options.terminal_options.override_pty_config(&mut pty_config);
 
@@ -210,14 +230,20 @@ impl WindowContext {
         // Create the PTY.
         //
         // The PTY forks a process to run the shell on the slave side of the
-        // pseudoterminal. A file descriptor for the master side is retained for
-        // reading/writing to the shell.
-        let pty = tty::new(&pty_config, display.size_info.into(), display.window.id().into())?;
+        // pseudoterminal, unless a different backend was selected. A file descriptor
+        // for the master side is retained for reading/writing to the shell.
+        let terminal_options = options.terminal_options;
+        let pty = pty_backend.create(&PtyContext {
+            pty_config: &pty_config,
+            terminal_options: &terminal_options,
+            window_size: display.size_info.into(),
+            window_id: display.window.id(),
+        })?;
 
         #[cfg(not(windows))]
         let master_fd = pty.file().as_raw_fd();
         #[cfg(not(windows))]
-        let shell_pid = pty.child().id();
+        let shell_pid = pty.child().map_or(0, |child| child.id());
 
         // 如未指定工作目录，尝试用当前前台进程工作目录设置标题（macOS/Unix）。
         #[cfg(not(windows))]
@@ -255,8 +281,13 @@ impl WindowContext {
             event_proxy.send_event(TerminalEvent::CursorBlinkingChange.into());
         }
 
+        let spill = config.scrolling.spill;
+        let scrollback_log =
+            spill.enabled.then(|| ScrollbackLog::new(display.window.id(), spill)).flatten();
+
         // Create context for the Alacritty window.
         Ok(WindowContext {
+            scrollback_log,
             preserve_title,
             terminal,
             display,
@@ -268,9 +299,15 @@ impl WindowContext {
             last_cwd_title: Default::default(),
             config,
             notifier: Notifier(loop_tx),
+            event_proxy,
+            pty_config,
+            terminal_options,
+            pty_backend,
+            last_output: Default::default(),
             cursor_blink_timed_out: Default::default(),
             prev_bell_cmd: Default::default(),
             inline_search_state: Default::default(),
+            vi_command_state: Default::default(),
             message_buffer: Default::default(),
             window_config: Default::default(),
             search_state: Default::default(),
@@ -286,6 +323,10 @@ impl WindowContext {
     /// 强制从前台进程工作目录更新标题（macOS/Unix）。
     #[cfg(not(windows))]
     pub fn update_title_from_foreground_cwd(&mut self) {
+        if self.update_templated_title() {
+            return;
+        }
+
         if let Ok(path) = foreground_process_path(self.master_fd, self.shell_pid) {
             let title = path.to_string_lossy().into_owned();
             let title = crate::path_util::shorten_home(&title);
@@ -296,6 +337,240 @@ impl WindowContext {
         }
     }
 
+    /// Update the title from the configured template.
+    ///
+    /// Returns `false` when the title isn't generated from a template.
+    pub fn update_templated_title(&mut self) -> bool {
+        if self.preserve_title {
+            return false;
+        }
+
+        let terminal = self.terminal.lock();
+        title::update_templated_title(
+            &self.config,
+            &mut self.display,
+            &terminal,
+            #[cfg(not(windows))]
+            self.master_fd,
+            #[cfg(not(windows))]
+            self.shell_pid,
+        )
+    }
+
+    /// Update the templated title after terminal output, to pick up new foreground processes.
+    ///
+    /// Updates are delayed, so bursts of output only cause a single update.
+    pub fn schedule_title_update(&mut self, scheduler: &mut Scheduler) {
+        let timer_id = TimerId::new(Topic::UpdateTitle, self.id());
+        if self.config.window.title_template.is_none() || scheduler.scheduled(timer_id) {
+            return;
+        }
+
+        let event = Event::new(EventType::UpdateTitle, self.id());
+        scheduler.schedule(event, title::UPDATE_INTERVAL, false, timer_id);
+    }
+
+    /// Check the terminal for new output, alerting on activity of unfocused windows.
+    pub fn monitor_output(&mut self, scheduler: &mut Scheduler) {
+        let monitor = &self.config.terminal.monitor;
+        if !monitor.activity && monitor.silence().is_none() {
+            return;
+        }
+
+        let terminal = self.terminal.lock();
+        let (last_output, focused) = (terminal.last_output(), terminal.is_focused);
+        drop(terminal);
+
+        if last_output.is_none() || last_output == self.last_output {
+            return;
+        }
+        self.last_output = last_output;
+
+        // Start counting down to silence, the timer is extended once it expires.
+        let monitor = &self.config.terminal.monitor;
+        let timer_id = TimerId::new(Topic::Silence, self.id());
+        if let Some(silence) = monitor.silence().filter(|_| !scheduler.scheduled(timer_id)) {
+            let event = Event::new(EventType::CheckSilence, self.id());
+            scheduler.schedule(event, silence, false, timer_id);
+        }
+
+        if monitor.activity
+            && should_alert(MonitorAlert::Activity, focused, self.display.window.alert())
+        {
+            self.alert(MonitorAlert::Activity);
+        }
+    }
+
+    /// Alert on silence of unfocused windows once no output was read for the configured duration.
+    pub fn check_silence(&mut self, scheduler: &mut Scheduler) {
+        let Some(silence) = self.config.terminal.monitor.silence() else { return };
+
+        let terminal = self.terminal.lock();
+        let (last_output, focused) = (terminal.last_output(), terminal.is_focused);
+        drop(terminal);
+
+        let Some(elapsed) = last_output.map(|last_output| last_output.elapsed()) else { return };
+        match silence_remaining(silence, elapsed) {
+            Some(remaining) => {
+                let event = Event::new(EventType::CheckSilence, self.id());
+                let timer_id = TimerId::new(Topic::Silence, self.id());
+                scheduler.schedule(event, remaining, false, timer_id);
+            },
+            None if should_alert(MonitorAlert::Silence, focused, self.display.window.alert()) => {
+                self.alert(MonitorAlert::Silence);
+            },
+            None => (),
+        }
+    }
+
+    /// Run the configured actions of an output monitoring alert.
+    fn alert(&mut self, alert: MonitorAlert) {
+        let monitor = &self.config.terminal.monitor;
+        let window = &mut self.display.window;
+
+        window.set_alert(Some(alert));
+
+        if monitor.urgent {
+            window.set_urgent(true);
+        }
+
+        if monitor.bell {
+            self.event_proxy.send_event(TerminalEvent::Bell.into());
+        }
+
+        if let Some(command) = &monitor.command {
+            let mut args: Vec<&str> = command.args().iter().map(String::as_str).collect();
+            args.extend([alert.as_str(), window.title()]);
+
+            #[cfg(not(windows))]
+            let result = spawn_daemon(command.program(), &args, self.master_fd, self.shell_pid);
+            #[cfg(windows)]
+            let result = spawn_daemon(command.program(), &args);
+
+            if let Err(err) = result {
+                warn!("Unable to launch {} with args {args:?}: {err}", command.program());
+            }
+        }
+
+        self.dirty = true;
+    }
+
+    /// Check whether the window should stay open once the shell exits.
+    pub fn holds_on_exit(&self) -> bool {
+        let window = &self.display.window;
+        holds_on_exit(window.hold, window.hold_on_error, self.display.exit_code)
+    }
+
+    /// Indicate the exit of the shell in a window which is held open.
+    pub fn child_exited(&mut self) {
+        self.display.child_exited = true;
+        self.update_templated_title();
+        self.display.damage_tracker.frame().mark_fully_damaged();
+        self.dirty = true;
+        if self.display.window.has_frame {
+            self.display.window.request_redraw();
+        }
+    }
+
+    /// Restart the shell in place, keeping the terminal's content.
+    pub fn respawn(&mut self) -> Result<(), Box<dyn Error>> {
+        #[cfg(not(windows))]
+        if self.terminal_options.attach_fd.is_some() {
+            return Err("terminals attached to a file descriptor can't be restarted".into());
+        }
+
+        let pty = self.pty_backend.create(&PtyContext {
+            pty_config: &self.pty_config,
+            terminal_options: &self.terminal_options,
+            window_size: self.display.size_info.into(),
+            window_id: self.id(),
+        })?;
+
+        #[cfg(not(windows))]
+        let (master_fd, shell_pid) =
+            (pty.file().as_raw_fd(), pty.child().map_or(0, |child| child.id()));
+
+        let event_loop = PtyEventLoop::new(
+            Arc::clone(&self.terminal),
+            self.event_proxy.clone(),
+            pty,
+            self.pty_config.drain_on_exit,
+            self.config.debug.ref_test,
+        )?;
+
+        // Processes of exited shells were already reaped, so their IDs could be reused.
+        #[cfg(not(windows))]
+        let foreground_pid = (!self.display.child_exited)
+            .then(|| foreground_process_id(self.master_fd, self.shell_pid));
+
+        // Shutdown the previous PTY before killing its processes, to avoid closing the window.
+        let notifier = Notifier(event_loop.channel());
+        let _ = mem::replace(&mut self.notifier, notifier).0.send(Msg::Shutdown);
+
+        #[cfg(not(windows))]
+        {
+            if let Some(foreground_pid) = foreground_pid {
+                kill_process_groups(foreground_pid, self.shell_pid);
+            }
+
+            self.master_fd = master_fd;
+            self.shell_pid = shell_pid;
+        }
+
+        self.insert_separator();
+        let _io_thread = event_loop.spawn();
+
+        self.display.child_exited = false;
+        self.display.exit_code = None;
+        self.display.damage_tracker.frame().mark_fully_damaged();
+        self.update_templated_title();
+        self.dirty = true;
+
+        Ok(())
+    }
+
+    /// Send a signal to the foreground process group of the terminal.
+    #[cfg(unix)]
+    pub fn signal(&self, signal: Signal) {
+        let _ = self.notifier.0.send(Msg::Signal(signal.number()));
+    }
+
+    /// Separate the previous shell's output from the new shell.
+    fn insert_separator(&mut self) {
+        let mut terminal = self.terminal.lock();
+
+        // Reset state left behind by programs of the previous shell.
+        terminal.reset_modes();
+        terminal.set_working_directory(None);
+        terminal.terminal_attribute(Attr::Reset);
+
+        if terminal.grid().cursor.point.column.0 != 0 {
+            terminal.carriage_return();
+            terminal.linefeed();
+        }
+
+        terminal.terminal_attribute(Attr::Dim);
+        for _ in 0..terminal.columns() {
+            terminal.input('─');
+        }
+        terminal.terminal_attribute(Attr::Reset);
+        terminal.carriage_return();
+        terminal.linefeed();
+    }
+
+    /// Append lines rotated out of the scrollback history to the scrollback log.
+    pub fn write_scrollback_log(&mut self) {
+        if !self.config.scrolling.spill.enabled {
+            return;
+        }
+
+        // Always take the lines, to avoid collecting them when the log couldn't be created.
+        let text = self.terminal.lock().take_evicted_text();
+        if let Some(scrollback_log) = self.scrollback_log.as_mut().filter(|_| !text.is_empty()) {
+            scrollback_log.write(&text);
+        }
+    }
+
     /// Update the terminal window to the latest config.
     pub fn update_config(&mut self, new_config: Rc<UiConfig>) {
         let old_config = mem::replace(&mut self.config, new_config);
@@ -306,6 +581,17 @@ impl WindowContext {
         self.display.update_config(&self.config);
         self.terminal.lock().set_options(self.config.term_options());
 
+        // Update the scrollback log, keeping its file if it's still enabled.
+        let spill = self.config.scrolling.spill;
+        match &mut self.scrollback_log {
+            Some(_) if !spill.enabled => self.scrollback_log = None,
+            Some(scrollback_log) => scrollback_log.update_config(spill),
+            None if spill.enabled => {
+                self.scrollback_log = ScrollbackLog::new(self.display.window.id(), spill);
+            },
+            None => (),
+        }
+
         // Reload cursor if its thickness has changed.
         if (old_config.cursor.thickness() - self.config.cursor.thickness()).abs() > f32::EPSILON {
             self.display.pending_update.set_cursor_dirty();
@@ -325,11 +611,13 @@ impl WindowContext {
         // Always reload the theme to account for auto-theme switching.
         self.display.window.set_theme(self.config.window.theme());
 
-        // Update display if either padding options or resize increments were changed.
+        // Update display if padding options, resize increments or the line number gutter changed.
         let window_config = &old_config.window;
         if window_config.padding(1.) != self.config.window.padding(1.)
             || window_config.dynamic_padding != self.config.window.dynamic_padding
             || window_config.resize_increments != self.config.window.resize_increments
+            || old_config.scrolling.line_numbers != self.config.scrolling.line_numbers
+            || old_config.scrolling.history() != self.config.scrolling.history()
         {
             self.display.pending_update.dirty = true;
         }
@@ -433,6 +721,7 @@ impl WindowContext {
             &self.message_buffer,
             &self.config,
             &mut self.search_state,
+            &self.vi_command_state,
         );
     }
 
@@ -470,6 +759,7 @@ impl WindowContext {
             prev_bell_cmd: &mut self.prev_bell_cmd,
             message_buffer: &mut self.message_buffer,
             inline_search_state: &mut self.inline_search_state,
+            vi_command_state: &mut self.vi_command_state,
             search_state: &mut self.search_state,
             modifiers: &mut self.modifiers,
             notifier: &mut self.notifier,
@@ -498,7 +788,8 @@ impl WindowContext {
         }
 
         // Process DisplayUpdate events.
-        if self.display.pending_update.dirty {
+        let display_updated = self.display.pending_update.dirty;
+        if display_updated {
             Self::submit_display_update(
                 &mut terminal,
                 &mut self.display,
@@ -530,6 +821,12 @@ impl WindowContext {
         {
             self.display.window.request_redraw();
         }
+
+        // Keep the dimensions in the title up to date.
+        if display_updated {
+            drop(terminal);
+            self.update_templated_title();
+        }
     }
 
     /// ID of this terminal context.
@@ -618,7 +915,67 @@ impl WindowContext {
 
 impl Drop for WindowContext {
     fn drop(&mut self) {
+        // Write lines which were evicted since the last wakeup.
+        self.write_scrollback_log();
+
         // Shutdown the terminal's PTY.
         let _ = self.notifier.0.send(Msg::Shutdown);
     }
 }
+
+/// Check whether a window should stay open once its shell exits.
+///
+/// Shells killed by a signal have no exit code, which is treated as an error.
+fn holds_on_exit(hold: bool, hold_on_error: bool, exit_code: Option<i32>) -> bool {
+    hold || (hold_on_error && exit_code != Some(0))
+}
+
+/// Time left until the output is silent, or `None` once it has been silent long enough.
+fn silence_remaining(silence: Duration, elapsed: Duration) -> Option<Duration> {
+    silence.checked_sub(elapsed).filter(|remaining| !remaining.is_zero())
+}
+
+/// Check whether a monitoring alert should be raised.
+///
+/// Focused windows are never alerted and an alert isn't repeated until it was cleared.
+fn should_alert(alert: MonitorAlert, focused: bool, current: Option<MonitorAlert>) -> bool {
+    !focused && current != Some(alert)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hold_on_error() {
+        assert!(!holds_on_exit(false, true, Some(0)));
+        assert!(holds_on_exit(false, true, Some(1)));
+        assert!(holds_on_exit(false, true, None));
+
+        assert!(holds_on_exit(true, false, Some(0)));
+        assert!(!holds_on_exit(false, false, None));
+    }
+
+    #[test]
+    fn silence_rearming() {
+        let silence = Duration::from_secs(10);
+
+        // Output during the countdown extends it by the time since the last output.
+        assert_eq!(silence_remaining(silence, Duration::ZERO), Some(silence));
+        let remaining = silence_remaining(silence, Duration::from_secs(3));
+        assert_eq!(remaining, Some(Duration::from_secs(7)));
+
+        assert_eq!(silence_remaining(silence, silence), None);
+        assert_eq!(silence_remaining(silence, Duration::from_secs(11)), None);
+    }
+
+    #[test]
+    fn focused_alerts() {
+        assert!(!should_alert(MonitorAlert::Activity, true, None));
+        assert!(!should_alert(MonitorAlert::Silence, true, None));
+
+        assert!(should_alert(MonitorAlert::Activity, false, None));
+        assert!(should_alert(MonitorAlert::Activity, false, Some(MonitorAlert::Silence)));
+        assert!(!should_alert(MonitorAlert::Activity, false, Some(MonitorAlert::Activity)));
+    }
+}
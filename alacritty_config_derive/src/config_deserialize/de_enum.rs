@@ -12,6 +12,7 @@ pub fn derive_deserialize(ident: Ident, generics: Generics, data_enum: DataEnum)
     // Create match arm streams and get a list with all available values.
     let mut match_arms_stream = TokenStream2::new();
     let mut available_values = String::from("one of ");
+    let mut variants = Vec::new();
     for variant in data_enum.variants.iter().filter(|variant| {
         // Skip deserialization for `#[config(skip)]` fields.
         variant.attrs.iter().all(|attr| {
@@ -24,6 +25,7 @@ pub fn derive_deserialize(ident: Ident, generics: Generics, data_enum: DataEnum)
         let variant_ident = &variant.ident;
         let variant_str = variant_ident.to_string();
         available_values = format!("{available_values}`{variant_str}`, ");
+        variants.push(variant_str.clone());
 
         let literal = variant_str.to_lowercase();
 
@@ -68,6 +70,16 @@ pub fn derive_deserialize(ident: Ident, generics: Generics, data_enum: DataEnum)
         }
     };
 
+    // List all deserializable variants.
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    tokens.extend(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Names of all variants which can be deserialized.
+            #[allow(dead_code)]
+            pub const VARIANTS: &'static [&'static str] = &[#(#variants),*];
+        }
+    });
+
     // Automatically implement [`alacritty_config::SerdeReplace`].
     tokens.extend(serde_replace::derive_direct(ident, generics));
 
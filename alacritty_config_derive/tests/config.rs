@@ -115,6 +115,7 @@ fn config_deserialize() {
     assert_eq!(test.field3, Some(32));
     assert_eq!(test.enom_small, TestEnum::One);
     assert_eq!(test.enom_big, TestEnum::Three);
+    assert_eq!(TestEnum::VARIANTS, ["One", "Two", "Three"]);
     assert_eq!(test.enom_error, Test::default().enom_error);
     assert!(!test.gone);
     assert_eq!(test.nesting.field1, Test::default().nesting.field1);
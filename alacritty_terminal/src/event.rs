@@ -3,6 +3,7 @@ use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 
 use crate::term::ClipboardType;
+use crate::term::window_ops::{WindowOp, WindowState};
 use crate::vte::ansi::Rgb;
 
 /// Terminal event.
@@ -41,6 +42,15 @@ pub enum Event {
     /// Request to write the text area size.
     TextAreaSizeRequest(Arc<dyn Fn(WindowSize) -> String + Sync + Send + 'static>),
 
+    /// Request to write information about the window.
+    ///
+    /// The attached function is a formatter which will correctly transform the window state into
+    /// the expected escape sequence format.
+    WindowStateRequest(Arc<dyn Fn(WindowState) -> String + Sync + Send + 'static>),
+
+    /// Request to manipulate the window.
+    WindowOp(WindowOp),
+
     /// Cursor blinking state has changed.
     CursorBlinkingChange,
 
@@ -63,6 +73,8 @@ impl Debug for Event {
             Event::ClipboardStore(ty, text) => write!(f, "ClipboardStore({ty:?}, {text})"),
             Event::ClipboardLoad(ty, _) => write!(f, "ClipboardLoad({ty:?})"),
             Event::TextAreaSizeRequest(_) => write!(f, "TextAreaSizeRequest"),
+            Event::WindowStateRequest(_) => write!(f, "WindowStateRequest"),
+            Event::WindowOp(op) => write!(f, "WindowOp({op:?})"),
             Event::ColorRequest(index, _) => write!(f, "ColorRequest({index})"),
             Event::PtyWrite(text) => write!(f, "PtyWrite({text})"),
             Event::Title(title) => write!(f, "Title({title})"),
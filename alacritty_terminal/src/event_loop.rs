@@ -17,6 +17,7 @@ use polling::{Event as PollingEvent, Events, PollMode};
 use crate::event::{self, Event, EventListener, WindowSize};
 use crate::sync::FairMutex;
 use crate::term::Term;
+use crate::term::sequence::{Sequence, SequenceScanner};
 use crate::{thread, tty};
 use vte::ansi;
 
@@ -37,6 +38,9 @@ pub enum Msg {
 
     /// Instruction to resize the PTY.
     Resize(WindowSize),
+
+    /// Signal which should be sent to the foreground process group of the PTY.
+    Signal(i32),
 }
 
 /// The main event loop.
@@ -93,6 +97,11 @@ where
             match msg {
                 Msg::Input(input) => state.write_list.push_back(input),
                 Msg::Resize(window_size) => self.pty.on_resize(window_size),
+                Msg::Signal(signal) => {
+                    if let Err(err) = self.pty.signal_foreground(signal) {
+                        error!("Unable to signal foreground process: {err}");
+                    }
+                },
                 Msg::Shutdown => return false,
             }
         }
@@ -120,6 +129,10 @@ where
         loop {
             // Read from the PTY.
             match self.pty.reader().read(&mut buf[unprocessed..]) {
+                // Without a child process, this indicates that the PTY hung up.
+                Ok(0) if unprocessed == 0 && !self.pty.has_child() => {
+                    return Err(ErrorKind::UnexpectedEof.into());
+                },
                 // This is received on Windows/macOS when no more data is readable from the PTY.
                 Ok(0) if unprocessed == 0 => break,
                 Ok(got) => unprocessed += got,
@@ -150,8 +163,7 @@ where
                 writer.write_all(&buf[..unprocessed]).unwrap();
             }
 
-            // Parse the incoming bytes.
-            state.parser.advance(&mut **terminal, &buf[..unprocessed]);
+            state.parse(&mut **terminal, &buf[..unprocessed]);
 
             processed += unprocessed;
             unprocessed = 0;
@@ -162,6 +174,11 @@ where
             }
         }
 
+        // Track PTY activity for monitoring of output and silence.
+        if let Some(terminal) = &mut terminal {
+            terminal.record_output();
+        }
+
         // Queue terminal redraw unless all processed bytes were synchronized.
         if state.parser.sync_bytes_count() < processed && processed > 0 {
             self.event_proxy.send_event(Event::Wakeup);
@@ -243,7 +260,7 @@ where
 
                 // Handle synchronized update timeout.
                 if events.is_empty() && self.rx.peek().is_none() {
-                    state.parser.stop_sync(&mut *self.terminal.lock());
+                    state.stop_sync(&mut *self.terminal.lock());
                     self.event_proxy.send_event(Event::Wakeup);
                     continue;
                 }
@@ -271,14 +288,23 @@ where
                         },
 
                         tty::PTY_READ_WRITE_TOKEN => {
-                            if event.is_interrupt() {
-                                // Don't try to do I/O on a dead PTY.
+                            // Don't try to do I/O on a dead PTY, unless reading reports the
+                            // hangup of a PTY without child process.
+                            if event.is_interrupt() && self.pty.has_child() {
                                 continue;
                             }
 
-                            if event.readable {
+                            if event.readable || event.is_interrupt() {
                                 if let Err(err) = self.pty_read(&mut state, &mut buf, pipe.as_mut())
                                 {
+                                    // Without a child process there is no `Exited` event, so
+                                    // errors terminate the terminal directly.
+                                    if !self.pty.has_child() {
+                                        self.terminal.lock().exit();
+                                        self.event_proxy.send_event(Event::Wakeup);
+                                        break 'event_loop;
+                                    }
+
                                     // On Linux, a `read` on the master side of a PTY can fail
                                     // with `EIO` if the client side hangs up.  In that case,
                                     // just loop back round for the inevitable `Exited` event.
@@ -401,9 +427,48 @@ pub struct State {
     write_list: VecDeque<Cow<'static, [u8]>>,
     writing: Option<Writing>,
     parser: ansi::Processor,
+    scanner: SequenceScanner,
+    pending_sequences: VecDeque<Sequence>,
 }
 
 impl State {
+    /// Parse PTY output, handling sequences ignored by the parser after all preceding bytes.
+    fn parse<T: EventListener>(&mut self, terminal: &mut Term<T>, mut bytes: &[u8]) {
+        loop {
+            let sequence = self.scanner.advance(bytes);
+            let end = sequence.as_ref().map_or(bytes.len(), |(end, _)| *end);
+
+            self.parser.advance(terminal, &bytes[..end]);
+            self.handle_pending_sequences(terminal);
+
+            let Some((_, sequence)) = sequence else { break };
+
+            // Synchronized updates buffer the preceding bytes, so the sequence has to wait for them.
+            if self.parser.sync_bytes_count() > 0 {
+                self.pending_sequences.push_back(sequence);
+            } else {
+                terminal.handle_sequence(sequence);
+            }
+
+            bytes = &bytes[end..];
+        }
+    }
+
+    /// End the synchronized update after its timeout.
+    fn stop_sync<T: EventListener>(&mut self, terminal: &mut Term<T>) {
+        self.parser.stop_sync(terminal);
+        self.handle_pending_sequences(terminal);
+    }
+
+    /// Handle sequences received during a synchronized update, once it has ended.
+    fn handle_pending_sequences<T: EventListener>(&mut self, terminal: &mut Term<T>) {
+        if self.parser.sync_bytes_count() == 0 {
+            for sequence in self.pending_sequences.drain(..) {
+                terminal.handle_sequence(sequence);
+            }
+        }
+    }
+
     #[inline]
     fn ensure_next(&mut self) {
         if self.writing.is_none() {
@@ -483,3 +548,54 @@ impl<T> PeekableReceiver<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::term::Config;
+    use crate::term::test::{RecordingListener, TermSize};
+    use crate::term::window_ops::WindowOps;
+
+    fn pty_writes(listener: &RecordingListener) -> Vec<String> {
+        let events = listener.take_events().into_iter();
+        events
+            .filter_map(|event| if let Event::PtyWrite(text) = event { Some(text) } else { None })
+            .collect()
+    }
+
+    #[test]
+    fn synchronized_query() {
+        let listener = RecordingListener::default();
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, listener.clone());
+        let mut state = State::default();
+
+        // Queries wait for the synchronized update to apply the preceding bytes.
+        state.parse(&mut term, b"\x1b[?2026h\x1b[1m\x1bP$qm\x1b\\");
+        assert!(pty_writes(&listener).is_empty());
+
+        state.stop_sync(&mut term);
+        assert_eq!(pty_writes(&listener), ["\x1bP1$r0;1m\x1b\\"]);
+
+        // Replies stay behind the ones from the parser.
+        state.parse(&mut term, b"\x1b[?2026h\x1b[0m\x1b[5n\x1bP$qm\x1b\\\x1b[?2026l");
+        assert_eq!(pty_writes(&listener), ["\x1b[0n", "\x1bP1$r0m\x1b\\"]);
+    }
+
+    #[test]
+    fn synchronized_window_op() {
+        let listener = RecordingListener::default();
+        let size = TermSize::new(10, 5);
+        let window_ops = WindowOps { report_title: true, ..Default::default() };
+        let config = Config { window_ops, ..Default::default() };
+        let mut term = Term::new(config, &size, listener.clone());
+        let mut state = State::default();
+
+        state.parse(&mut term, b"\x1b[?2026h\x1b]2;vim\x07\x1b[21t");
+        assert!(pty_writes(&listener).is_empty());
+
+        state.parse(&mut term, b"\x1b[?2026l");
+        assert_eq!(pty_writes(&listener), ["\x1b]lvim\x1b\\"]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Compressed storage for lines rotated out of the scrollback history.
+//!
+//! Rows are stored as their text, with the remaining cell attributes run-length encoded. Since
+//! most lines share their attributes across all cells, this reduces the memory usage of a line to
+//! roughly its number of characters.
+
+use std::collections::VecDeque;
+use std::{iter, mem};
+
+use super::Row;
+use crate::index::Column;
+
+/// Cells which can be stored in the compressed history.
+pub trait CompressibleCell: Sized {
+    /// Split the cell into its character and a template holding all other attributes.
+    fn split(&self) -> (char, Self);
+
+    /// Create a cell from a character and a template returned by [`CompressibleCell::split`].
+    fn join(c: char, template: &Self) -> Self;
+}
+
+/// Function compressing a row.
+type Compressor<T> = fn(&Row<T>) -> CompressedRow<T>;
+
+/// Compressed lines, ordered from newest to oldest.
+#[derive(Clone, Debug)]
+pub struct CompressedHistory<T> {
+    rows: VecDeque<CompressedRow<T>>,
+
+    /// Estimated memory usage of all rows in bytes.
+    bytes: usize,
+
+    /// Maximum memory usage in bytes, before the oldest rows are dropped.
+    max_bytes: usize,
+
+    /// Compression of new rows.
+    ///
+    /// This is set together with the memory limit, so rows can be compressed while scrolling
+    /// without requiring [`CompressibleCell`] for every grid.
+    compress: Option<Compressor<T>>,
+}
+
+impl<T> Default for CompressedHistory<T> {
+    fn default() -> Self {
+        Self { rows: Default::default(), bytes: 0, max_bytes: 0, compress: None }
+    }
+}
+
+impl<T> CompressedHistory<T> {
+    /// Number of compressed lines.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if lines are kept after being compressed.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.max_bytes != 0
+    }
+
+    /// Update the maximum memory usage in bytes.
+    pub fn set_max_bytes(&mut self, max_bytes: usize)
+    where
+        T: CompressibleCell + PartialEq,
+    {
+        self.compress = Some(CompressedRow::new);
+        self.max_bytes = max_bytes;
+        self.enforce_limit();
+    }
+
+    /// Remove all compressed lines.
+    pub fn clear(&mut self) {
+        self.rows = VecDeque::new();
+        self.bytes = 0;
+    }
+
+    /// Compress a line, making it the newest one.
+    pub fn push(&mut self, row: &Row<T>) {
+        let compress = match self.compress {
+            Some(compress) if self.is_enabled() => compress,
+            _ => return,
+        };
+
+        let row = compress(row);
+        self.bytes += row.size();
+        self.rows.push_front(row);
+
+        self.enforce_limit();
+    }
+
+    /// Remove the newest line, decompressing it with the requested number of columns.
+    pub fn pop(&mut self, columns: usize) -> Option<Row<T>>
+    where
+        T: CompressibleCell + Default + PartialEq,
+    {
+        let row = self.rows.pop_front()?;
+        self.bytes -= row.size();
+        Some(row.decompress(columns))
+    }
+
+    /// Drop the oldest lines until the memory limit is satisfied.
+    fn enforce_limit(&mut self) {
+        while self.bytes > self.max_bytes {
+            match self.rows.pop_back() {
+                Some(row) => self.bytes -= row.size(),
+                None => break,
+            }
+        }
+
+        // Release memory after large reductions.
+        if self.rows.capacity() > 2 * self.rows.len() + 1024 {
+            self.rows.shrink_to_fit();
+        }
+    }
+}
+
+/// A single compressed line.
+#[derive(Clone, Debug)]
+struct CompressedRow<T> {
+    /// Characters of all cells.
+    text: Box<str>,
+
+    /// Cell templates, with the number of consecutive cells using them.
+    runs: Box<[(u32, T)]>,
+}
+
+impl<T> CompressedRow<T> {
+    fn new(row: &Row<T>) -> Self
+    where
+        T: CompressibleCell + PartialEq,
+    {
+        let mut text = String::with_capacity(row.len());
+        let mut runs: Vec<(u32, T)> = Vec::new();
+
+        for cell in row {
+            let (c, template) = cell.split();
+            text.push(c);
+
+            match runs.last_mut() {
+                Some((count, last)) if *last == template => *count += 1,
+                _ => runs.push((1, template)),
+            }
+        }
+
+        Self { text: text.into_boxed_str(), runs: runs.into_boxed_slice() }
+    }
+
+    /// Restore the row, truncating or padding it to the number of columns.
+    fn decompress(&self, columns: usize) -> Row<T>
+    where
+        T: CompressibleCell + Default + PartialEq,
+    {
+        let mut row = Row::new(columns);
+
+        let default = T::default();
+        let templates =
+            self.runs.iter().flat_map(|(count, template)| iter::repeat_n(template, *count as _));
+        for (i, (c, template)) in self.text.chars().zip(templates).take(columns).enumerate() {
+            let cell = T::join(c, template);
+            if cell != default {
+                row[Column(i)] = cell;
+            }
+        }
+
+        row
+    }
+
+    /// Estimated memory usage in bytes.
+    fn size(&self) -> usize {
+        mem::size_of::<Self>() + self.text.len() + mem::size_of_val(&*self.runs)
+    }
+}
@@ -1,6 +1,7 @@
 //! A specialized 2D grid implementation optimized for use in a terminal.
 
 use std::cmp::{max, min};
+use std::mem;
 use std::ops::{Bound, Deref, Index, IndexMut, Range, RangeBounds};
 
 #[cfg(feature = "serde")]
@@ -10,12 +11,15 @@ use crate::index::{Column, Line, Point};
 use crate::term::cell::{Flags, ResetDiscriminant};
 use crate::vte::ansi::{CharsetIndex, StandardCharset};
 
+mod compressed;
 pub mod resize;
 mod row;
 mod storage;
 #[cfg(test)]
 mod tests;
 
+use self::compressed::CompressedHistory;
+pub use self::compressed::CompressibleCell;
 pub use self::row::Row;
 use self::storage::Storage;
 
@@ -78,6 +82,9 @@ pub enum Scroll {
     Bottom,
 }
 
+/// Maximum number of compressed lines restored beyond the scrollback history limit.
+pub const MAX_THAWED_LINES: usize = 100_000;
+
 /// Grid based terminal content storage.
 ///
 /// ```notrust
@@ -135,6 +142,23 @@ pub struct Grid<T> {
 
     /// Maximum number of lines in history.
     max_scroll_limit: usize,
+
+    /// Number of lines rotated into the scrollback history.
+    ///
+    /// This allows tracking positions across output, since a point's line plus this counter
+    /// stays the same while its row is moved up by new lines.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scrolled_lines: u64,
+
+    /// Lines rotated out of the scrollback history.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    compressed: CompressedHistory<T>,
+
+    /// Lines rotated out of the scrollback history since they were last taken.
+    ///
+    /// This is `None` unless collection was requested using [`Grid::set_collect_evicted`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    evicted: Option<Vec<Row<T>>>,
 }
 
 impl<T: GridCell + Default + PartialEq> Grid<T> {
@@ -145,12 +169,18 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
             display_offset: 0,
             saved_cursor: Cursor::default(),
             cursor: Cursor::default(),
+            scrolled_lines: 0,
+            compressed: Default::default(),
+            evicted: None,
             lines,
             columns,
         }
     }
 
     /// Update the size of the scrollback history.
+    ///
+    /// Lines restored from the compressed history are dropped if they exceed the new size, so
+    /// they should be compressed using [`Grid::freeze_history`] first.
     pub fn update_history(&mut self, history_size: usize) {
         let current_history_size = self.history_size();
         if current_history_size > history_size {
@@ -160,6 +190,23 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
         self.max_scroll_limit = history_size;
     }
 
+    /// Enable or disable collection of lines rotated out of the scrollback history.
+    ///
+    /// Lines restored from the compressed history are only collected once, when they first leave
+    /// the scrollback history.
+    pub fn set_collect_evicted(&mut self, enabled: bool) {
+        match (enabled, self.evicted.is_some()) {
+            (true, false) => self.evicted = Some(Vec::new()),
+            (false, true) => self.evicted = None,
+            _ => (),
+        }
+    }
+
+    /// Take all lines collected since the last call, ordered from oldest to newest.
+    pub fn take_evicted(&mut self) -> Vec<Row<T>> {
+        self.evicted.as_mut().map(mem::take).unwrap_or_default()
+    }
+
     pub fn scroll_display(&mut self, scroll: Scroll) {
         self.display_offset = match scroll {
             Scroll::Delta(count) => {
@@ -173,7 +220,7 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
     }
 
     fn increase_scroll_limit(&mut self, count: usize) {
-        let count = min(count, self.max_scroll_limit - self.history_size());
+        let count = min(count, self.max_scroll_limit.saturating_sub(self.history_size()));
         if count != 0 {
             self.raw.initialize(count, self.columns);
         }
@@ -265,13 +312,36 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
 
         // Update display offset when not pinned to active area.
         if self.display_offset != 0 {
-            self.display_offset = min(self.display_offset + positions, self.max_scroll_limit);
+            let max_offset = max(self.max_scroll_limit, self.history_size());
+            self.display_offset = min(self.display_offset + positions, max_offset);
         }
 
         // Only rotate the entire history if the active region starts at the top.
         if region.start == 0 {
+            // Compress and collect lines which are rotated out of the history.
+            let compress = self.compressed.is_enabled() && self.max_scroll_limit != 0;
+            if compress || self.evicted.is_some() {
+                let growth = self.max_scroll_limit.saturating_sub(self.history_size());
+                let evicted = min(positions.saturating_sub(growth), self.total_lines());
+                let thawed = self.history_size().saturating_sub(self.max_scroll_limit);
+                let topmost_line = self.topmost_line();
+                for i in 0..evicted {
+                    let line = topmost_line + i;
+
+                    if compress {
+                        self.compressed.push(&self.raw[line]);
+                    }
+
+                    // Restored lines have already been collected when they were first evicted.
+                    if let Some(collected) = self.evicted.as_mut().filter(|_| i >= thawed) {
+                        collected.push(mem::replace(&mut self.raw[line], Row::new(self.columns)));
+                    }
+                }
+            }
+
             // Create scrollback for the new lines.
             self.increase_scroll_limit(positions);
+            self.scrolled_lines = self.scrolled_lines.wrapping_add(positions as u64);
 
             // Swap the lines fixed at the top to their target positions after rotation.
             //
@@ -352,6 +422,68 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
     }
 }
 
+impl<T: GridCell + CompressibleCell + Default + PartialEq> Grid<T> {
+    /// Update the maximum memory usage of compressed history in bytes.
+    ///
+    /// Lines rotated out of the scrollback history are compressed and kept until this limit is
+    /// reached. A limit of zero disables the compressed history.
+    pub fn update_compressed_history(&mut self, max_bytes: usize) {
+        self.compressed.set_max_bytes(max_bytes);
+    }
+
+    /// Number of lines in the compressed history.
+    #[inline]
+    pub fn compressed_lines(&self) -> usize {
+        self.compressed.len()
+    }
+
+    /// Restore lines from the compressed history above the scrollback history.
+    ///
+    /// At most [`MAX_THAWED_LINES`] are restored beyond the history limit. Returns the number of
+    /// restored lines.
+    pub fn thaw_history(&mut self, count: usize) -> usize {
+        let thawed = self.history_size().saturating_sub(self.max_scroll_limit);
+        let count = min(count, MAX_THAWED_LINES.saturating_sub(thawed));
+        let count = min(count, self.compressed.len());
+
+        // Restored lines need an active history, since they're part of it.
+        if count == 0 || self.max_scroll_limit == 0 {
+            return 0;
+        }
+
+        // Newest lines are restored at the bottom of the new space.
+        self.raw.initialize(count, self.columns);
+        let topmost_line = self.topmost_line();
+        for i in (0..count).rev() {
+            let row = self.compressed.pop(self.columns).unwrap();
+            self.raw[topmost_line + i] = row;
+        }
+
+        count
+    }
+
+    /// Compress restored lines which exceed the scrollback history limit.
+    ///
+    /// Returns the number of compressed lines.
+    pub fn freeze_history(&mut self) -> usize {
+        let count = self.history_size().saturating_sub(self.max_scroll_limit);
+        if count == 0 {
+            return 0;
+        }
+
+        // Compress oldest lines first, so the newest one ends up in front.
+        let topmost_line = self.topmost_line();
+        for i in 0..count {
+            self.compressed.push(&self.raw[topmost_line + i]);
+        }
+
+        self.raw.shrink_lines(count);
+        self.display_offset = min(self.display_offset, self.history_size());
+
+        count
+    }
+}
+
 impl<T> Grid<T> {
     /// Reset a visible region within the grid.
     pub fn reset_region<D, R: RangeBounds<Line>>(&mut self, bounds: R)
@@ -383,6 +515,7 @@ impl<T> Grid<T> {
     pub fn clear_history(&mut self) {
         // Explicitly purge all lines from history.
         self.raw.shrink_lines(self.history_size());
+        self.compressed.clear();
 
         // Reset display offset.
         self.display_offset = 0;
@@ -433,6 +566,16 @@ impl<T> Grid<T> {
         self.display_offset
     }
 
+    /// Number of lines rotated into the scrollback history.
+    ///
+    /// Adding a point's line to this value results in a position which is stable across new
+    /// output. The counter wraps around, so positions should be compared using wrapping
+    /// arithmetic.
+    #[inline]
+    pub fn scrolled_lines(&self) -> u64 {
+        self.scrolled_lines
+    }
+
     #[inline]
     pub fn cursor_cell(&mut self) -> &mut T {
         let point = self.cursor.point;
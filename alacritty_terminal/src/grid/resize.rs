@@ -51,6 +51,9 @@ impl<T: GridCell + Default + PartialEq> Grid<T> {
         self.raw.grow_visible_lines(target);
         self.lines = target;
 
+        // Growing the viewport moves all existing lines down.
+        self.scrolled_lines = self.scrolled_lines.wrapping_sub(lines_added as u64);
+
         let history_size = self.history_size();
         let from_history = min(history_size, lines_added);
 
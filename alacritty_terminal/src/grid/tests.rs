@@ -22,6 +22,16 @@ impl GridCell for usize {
     }
 }
 
+impl CompressibleCell for usize {
+    fn split(&self) -> (char, Self) {
+        (' ', *self)
+    }
+
+    fn join(_c: char, template: &Self) -> Self {
+        *template
+    }
+}
+
 // Scroll up moves lines upward.
 #[test]
 fn scroll_up() {
@@ -54,6 +64,74 @@ fn scroll_up() {
     assert_eq!(grid[Line(9)].occ, 0);
 }
 
+// Scrolled lines stay addressable relative to the scroll counter.
+#[test]
+fn scrolled_lines() {
+    let mut grid = Grid::<usize>::new(10, 1, 2);
+    for i in 0..10 {
+        grid[Line(i as i32)][Column(0)] = i;
+    }
+
+    grid.scroll_up::<usize>(&(Line(0)..Line(10)), 1);
+    assert_eq!(grid.scrolled_lines(), 1);
+    assert_eq!(grid[Line(4 - grid.scrolled_lines() as i32)][Column(0)], 4);
+
+    // Scrolling beyond the history limit keeps the counter increasing.
+    grid.scroll_up::<usize>(&(Line(0)..Line(10)), 3);
+    assert_eq!(grid.scrolled_lines(), 4);
+    assert_eq!(grid[Line(4 - grid.scrolled_lines() as i32)][Column(0)], 4);
+
+    // Subregion scrolling doesn't touch the history.
+    grid.scroll_up::<usize>(&(Line(5)..Line(10)), 1);
+    assert_eq!(grid.scrolled_lines(), 4);
+}
+
+#[test]
+fn compressed_history() {
+    let mut grid = Grid::<usize>::new(3, 1, 2);
+    grid.update_compressed_history(1024 * 1024);
+    for i in 0..3 {
+        grid[Line(i)][Column(0)] = i as usize + 1;
+    }
+
+    for value in 4..=7 {
+        grid.scroll_up::<usize>(&(Line(0)..Line(3)), 1);
+        grid[Line(2)][Column(0)] = value;
+    }
+
+    // Lines rotated out of the history are compressed.
+    assert_eq!(grid.history_size(), 2);
+    assert_eq!(grid.compressed_lines(), 2);
+    assert_eq!(grid[grid.topmost_line()][Column(0)], 3);
+
+    // Thawing restores them.
+    assert_eq!(grid.thaw_history(usize::MAX), 2);
+    grid.scroll_display(Scroll::Top);
+    assert_eq!(grid.history_size(), 4);
+    assert_eq!(grid.display_offset(), 4);
+    assert_eq!(grid.compressed_lines(), 0);
+    assert_eq!(grid[grid.topmost_line()][Column(0)], 1);
+    assert_eq!(grid[Line(-3)][Column(0)], 2);
+
+    // New output keeps the restored lines while scrolled up.
+    grid.scroll_up::<usize>(&(Line(0)..Line(3)), 1);
+    grid[Line(2)][Column(0)] = 8;
+    assert_eq!(grid.history_size(), 4);
+    assert_eq!(grid.display_offset(), 4);
+    assert_eq!(grid.compressed_lines(), 1);
+    assert_eq!(grid[grid.topmost_line()][Column(0)], 2);
+
+    // Freezing compresses everything beyond the history limit.
+    assert_eq!(grid.freeze_history(), 2);
+    assert_eq!(grid.history_size(), 2);
+    assert_eq!(grid.display_offset(), 2);
+    assert_eq!(grid.compressed_lines(), 3);
+    assert_eq!(grid[grid.topmost_line()][Column(0)], 4);
+
+    assert_eq!(grid.thaw_history(1), 1);
+    assert_eq!(grid[grid.topmost_line()][Column(0)], 3);
+}
+
 // Scroll down moves lines downward.
 #[test]
 fn scroll_down() {
@@ -95,6 +95,7 @@ pub enum SelectionType {
     Block,
     Semantic,
     Lines,
+    Rows,
 }
 
 /// Describes a region of a 2-dimensional area.
@@ -220,7 +221,7 @@ impl Selection {
                         && start.side == Side::Left
                         && end.side == Side::Right)
             },
-            SelectionType::Semantic | SelectionType::Lines => false,
+            SelectionType::Semantic | SelectionType::Lines | SelectionType::Rows => false,
         }
     }
 
@@ -292,6 +293,7 @@ impl Selection {
             SelectionType::Block => self.range_block(start, end),
             SelectionType::Semantic => Some(Self::range_semantic(term, start.point, end.point)),
             SelectionType::Lines => Some(Self::range_lines(term, start.point, end.point)),
+            SelectionType::Rows => Some(Self::range_rows(term, start.point, end.point)),
         }
     }
 
@@ -323,6 +325,13 @@ impl Selection {
         SelectionRange { start, end, is_block: false }
     }
 
+    fn range_rows<T>(term: &Term<T>, mut start: Point, mut end: Point) -> SelectionRange {
+        start.column = Column(0);
+        end.column = term.last_column();
+
+        SelectionRange { start, end, is_block: false }
+    }
+
     fn range_simple(
         &self,
         mut start: Anchor,
@@ -531,6 +540,23 @@ mod tests {
         });
     }
 
+    #[test]
+    fn row_selection() {
+        let size = (10, 5);
+        let mut term = term(size.0, size.1);
+        term.grid_mut()[Line(2)][Column(4)].flags.insert(Flags::WRAPLINE);
+
+        let mut selection =
+            Selection::new(SelectionType::Rows, Point::new(Line(3), Column(1)), Side::Left);
+        selection.update(Point::new(Line(4), Column(2)), Side::Right);
+
+        assert_eq!(selection.to_range(&term).unwrap(), SelectionRange {
+            start: Point::new(Line(3), Column(0)),
+            end: Point::new(Line(4), Column(4)),
+            is_block: false,
+        });
+    }
+
     #[test]
     fn semantic_selection() {
         let size = (10, 5);
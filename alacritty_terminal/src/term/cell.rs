@@ -5,7 +5,7 @@ use bitflags::bitflags;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::grid::{self, GridCell};
+use crate::grid::{self, CompressibleCell, GridCell};
 use crate::index::Column;
 use crate::vte::ansi::{Color, Hyperlink as VteHyperlink, NamedColor};
 
@@ -254,6 +254,18 @@ impl GridCell for Cell {
     }
 }
 
+impl CompressibleCell for Cell {
+    #[inline]
+    fn split(&self) -> (char, Self) {
+        (self.c, Cell { c: ' ', ..self.clone() })
+    }
+
+    #[inline]
+    fn join(c: char, template: &Self) -> Self {
+        Cell { c, ..template.clone() }
+    }
+}
+
 impl From<Color> for Cell {
     #[inline]
     fn from(color: Color) -> Self {
@@ -0,0 +1,257 @@
+//! Device control string queries.
+//!
+//! The ANSI parser ignores all device control strings, so queries are extracted from the PTY
+//! output by the [`SequenceScanner`] and answered by the [`Term`] once all preceding bytes have
+//! been parsed.
+//!
+//! [`SequenceScanner`]: crate::term::sequence::SequenceScanner
+
+use std::fmt::Write;
+
+use log::{debug, trace};
+
+use crate::event::{Event, EventListener};
+use crate::term::Term;
+use crate::term::cell::Flags;
+use crate::vte::ansi::{Color, CursorShape};
+
+/// String terminator.
+const ST: &str = "\x1b\\";
+
+/// Terminal capabilities reported by XTGETTCAP, based on `extra/alacritty.info`.
+///
+/// Boolean capabilities have no value.
+const TERMCAP: &[(&str, Option<&str>)] = &[
+    ("TN", Some("alacritty")),
+    ("name", Some("alacritty")),
+    ("Co", Some("256")),
+    ("colors", Some("256")),
+    ("RGB", None),
+    ("Tc", None),
+    ("setrgbf", Some("\x1b[38;2;%p1%d;%p2%d;%p3%dm")),
+    ("setrgbb", Some("\x1b[48;2;%p1%d;%p2%d;%p3%dm")),
+    ("Smulx", Some("\x1b[4:%p1%dm")),
+    ("Setulc", Some("\x1b[58:2::%p1%{65536}%/%d:%p1%{256}%/%{255}%&%d:%p1%{255}%&%dm")),
+    ("Ss", Some("\x1b[%p1%d q")),
+    ("Se", Some("\x1b[0 q")),
+    ("Cs", Some("\x1b]12;%p1%s\x07")),
+    ("Cr", Some("\x1b]112\x07")),
+    ("Ms", Some("\x1b]52;%p1%s;%p2%s\x07")),
+    ("Sync", Some("\x1b[?2026%?%p1%{1}%-%tl%eh%;")),
+    ("BE", Some("\x1b[?2004h")),
+    ("BD", Some("\x1b[?2004l")),
+    ("PS", Some("\x1b[200~")),
+    ("PE", Some("\x1b[201~")),
+    ("XF", None),
+    ("smxx", Some("\x1b[9m")),
+    ("rmxx", Some("\x1b[29m")),
+];
+
+/// Device control string query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DcsQuery {
+    /// Request termcap/terminfo strings (XTGETTCAP), with hex encoded names separated by `;`.
+    Termcap(Vec<u8>),
+
+    /// Request status string (DECRQSS).
+    StatusString(Vec<u8>),
+}
+
+impl<T: EventListener> Term<T> {
+    /// Answer a device control string query.
+    pub fn dcs_query(&mut self, query: DcsQuery) {
+        match query {
+            DcsQuery::Termcap(names) => self.report_termcap(&names),
+            DcsQuery::StatusString(setting) => self.report_status_string(&setting),
+        }
+    }
+
+    /// Report terminal capabilities (XTGETTCAP).
+    fn report_termcap(&mut self, names: &[u8]) {
+        let mut text = String::new();
+        for hex_name in names.split(|&byte| byte == b';') {
+            // Never echo anything but hex digits, to prevent queries from injecting input.
+            if hex_name.is_empty() || !hex_name.iter().all(u8::is_ascii_hexdigit) {
+                debug!("Invalid termcap name {:?}", String::from_utf8_lossy(hex_name));
+                let _ = write!(text, "\x1bP0+r{ST}");
+                continue;
+            }
+
+            let hex_name = String::from_utf8_lossy(hex_name);
+            let capability = hex_decode(&hex_name).and_then(|name| {
+                TERMCAP.iter().find(|(capability, _)| *capability == name).map(|(_, value)| value)
+            });
+
+            trace!("Reporting termcap {hex_name}: {capability:?}");
+
+            match capability {
+                Some(Some(value)) => {
+                    let _ = write!(text, "\x1bP1+r{hex_name}={}{ST}", hex_encode(value));
+                },
+                Some(None) => {
+                    let _ = write!(text, "\x1bP1+r{hex_name}{ST}");
+                },
+                None => {
+                    let _ = write!(text, "\x1bP0+r{hex_name}{ST}");
+                },
+            }
+        }
+
+        self.event_proxy.send_event(Event::PtyWrite(text));
+    }
+
+    /// Report the current value of a setting (DECRQSS).
+    fn report_status_string(&mut self, setting: &[u8]) {
+        let value = match setting {
+            b"m" => Some(self.sgr_status()),
+            b" q" => {
+                let style = self.cursor_style.unwrap_or(self.config.default_cursor_style);
+                let shape = match style.shape {
+                    CursorShape::Underline => 3,
+                    CursorShape::Beam => 5,
+                    CursorShape::Block | CursorShape::HollowBlock | CursorShape::Hidden => 1,
+                };
+                Some(format!("{} q", shape + u8::from(!style.blinking)))
+            },
+            b"r" => {
+                Some(format!("{};{}r", self.scroll_region.start.0 + 1, self.scroll_region.end.0))
+            },
+            _ => None,
+        };
+
+        trace!("Reporting status string {:?}: {value:?}", String::from_utf8_lossy(setting));
+
+        let text = match value {
+            Some(value) => format!("\x1bP1$r{value}{ST}"),
+            None => {
+                debug!("Unsupported status string {:?}", String::from_utf8_lossy(setting));
+                format!("\x1bP0$r{ST}")
+            },
+        };
+        self.event_proxy.send_event(Event::PtyWrite(text));
+    }
+
+    /// SGR parameters matching the current cursor template.
+    fn sgr_status(&self) -> String {
+        let template = &self.grid.cursor.template;
+        let mut sgr = String::from("0");
+
+        for (flag, param) in [
+            (Flags::BOLD, "1"),
+            (Flags::DIM, "2"),
+            (Flags::ITALIC, "3"),
+            (Flags::UNDERLINE, "4"),
+            (Flags::DOUBLE_UNDERLINE, "4:2"),
+            (Flags::UNDERCURL, "4:3"),
+            (Flags::DOTTED_UNDERLINE, "4:4"),
+            (Flags::DASHED_UNDERLINE, "4:5"),
+            (Flags::INVERSE, "7"),
+            (Flags::HIDDEN, "8"),
+            (Flags::STRIKEOUT, "9"),
+        ] {
+            if template.flags.contains(flag) {
+                sgr.push(';');
+                sgr.push_str(param);
+            }
+        }
+
+        push_sgr_color(&mut sgr, template.fg, Some((30, 90)), 38);
+        push_sgr_color(&mut sgr, template.bg, Some((40, 100)), 48);
+        if let Some(color) = template.underline_color() {
+            push_sgr_color(&mut sgr, color, None, 58);
+        }
+
+        sgr.push('m');
+        sgr
+    }
+}
+
+/// Append SGR parameters for a color.
+///
+/// The `palette` offsets are used for the 16 palette colors, instead of their index. Other named
+/// colors are the defaults and don't need any parameters.
+fn push_sgr_color(sgr: &mut String, color: Color, palette: Option<(usize, usize)>, extended: u8) {
+    let _ = match (color, palette) {
+        (Color::Named(color), Some((normal, _))) if (color as usize) < 8 => {
+            write!(sgr, ";{}", normal + color as usize)
+        },
+        (Color::Named(color), Some((_, bright))) if (color as usize) < 16 => {
+            write!(sgr, ";{}", bright + color as usize - 8)
+        },
+        (Color::Named(color), None) if (color as usize) < 16 => {
+            write!(sgr, ";{extended};5;{}", color as usize)
+        },
+        (Color::Named(_), _) => Ok(()),
+        (Color::Indexed(index), _) => write!(sgr, ";{extended};5;{index}"),
+        (Color::Spec(rgb), _) => write!(sgr, ";{extended};2;{};{};{}", rgb.r, rgb.g, rgb.b),
+    };
+}
+
+/// Encode text as uppercase hexadecimal.
+fn hex_encode(text: &str) -> String {
+    text.bytes().fold(String::with_capacity(text.len() * 2), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02X}");
+        hex
+    })
+}
+
+/// Decode hexadecimal text.
+fn hex_decode(hex: &str) -> Option<String> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::event::VoidListener;
+    use crate::term::Config;
+    use crate::term::test::{RecordingListener, TermSize};
+    use crate::vte::ansi::{self, Handler, NamedColor};
+
+    #[test]
+    fn status_string() {
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        term.terminal_attribute(ansi::Attr::Bold);
+        term.terminal_attribute(ansi::Attr::Foreground(Color::Named(NamedColor::BrightRed)));
+        term.terminal_attribute(ansi::Attr::Background(Color::Indexed(100)));
+        assert_eq!(term.sgr_status(), "0;1;91;48;5;100m");
+
+        term.terminal_attribute(ansi::Attr::Reset);
+        assert_eq!(term.sgr_status(), "0m");
+    }
+
+    #[test]
+    fn termcap_injection() {
+        let listener = RecordingListener::default();
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, listener.clone());
+
+        term.dcs_query(DcsQuery::Termcap(b"\rrm -rf ~\r;;5463;5A5A".to_vec()));
+        match listener.take_events().as_slice() {
+            [Event::PtyWrite(text)] => {
+                assert_eq!(text, "\x1bP0+r\x1b\\\x1bP0+r\x1b\\\x1bP1+r5463\x1b\\\x1bP0+r5A5A\x1b\\")
+            },
+            events => panic!("unexpected events {events:?}"),
+        }
+    }
+
+    #[test]
+    fn hex_names() {
+        assert_eq!(hex_encode("Tc"), "5463");
+        assert_eq!(hex_decode("5463").as_deref(), Some("Tc"));
+        assert_eq!(hex_decode("546"), None);
+        assert_eq!(hex_decode("5G"), None);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::ops::{Index, IndexMut, Range};
 use std::sync::Arc;
+use std::time::Instant;
 use std::{cmp, mem, ptr, slice, str};
 
 #[cfg(feature = "serde")]
@@ -14,11 +15,13 @@ use log::{debug, trace};
 use unicode_width::UnicodeWidthChar;
 
 use crate::event::{Event, EventListener};
-use crate::grid::{Dimensions, Grid, GridIterator, Scroll};
+use crate::grid::{Dimensions, Grid, GridIterator, Row, Scroll};
 use crate::index::{self, Boundary, Column, Direction, Line, Point, Side};
 use crate::selection::{Selection, SelectionRange, SelectionType};
 use crate::term::cell::{Cell, Flags, LineLength};
 use crate::term::color::Colors;
+use crate::term::window_ops::WindowOps;
+use crate::term::working_directory::WorkingDirectory;
 use crate::vi_mode::{ViModeCursor, ViMotion};
 use crate::vte::ansi::{
     self, Attr, CharsetIndex, Color, CursorShape, CursorStyle, Handler, Hyperlink, KeyboardModes,
@@ -28,7 +31,11 @@ use crate::vte::ansi::{
 
 pub mod cell;
 pub mod color;
+pub mod dcs;
 pub mod search;
+pub mod sequence;
+pub mod window_ops;
+pub mod working_directory;
 
 /// Minimum number of columns.
 ///
@@ -327,6 +334,12 @@ pub struct Term<T> {
 
     /// Config directly for the terminal.
     config: Config,
+
+    /// Time of the last output read from the PTY.
+    last_output: Option<Instant>,
+
+    /// Working directory reported by the shell.
+    working_directory: Option<WorkingDirectory>,
 }
 
 /// Configuration options for the [`Term`].
@@ -335,6 +348,17 @@ pub struct Config {
     /// The maximum amount of scrolling history.
     pub scrolling_history: usize,
 
+    /// Maximum memory used for lines rotated out of the scrolling history, in bytes.
+    ///
+    /// These lines are kept in compressed form and restored when scrolling beyond the top of the
+    /// history. A value of zero disables the compressed history.
+    pub compressed_history: usize,
+
+    /// Collect lines rotated out of the scrolling history.
+    ///
+    /// Collected lines can be retrieved using [`Term::take_evicted_text`].
+    pub collect_evicted_lines: bool,
+
     /// Default cursor style to reset the cursor to.
     pub default_cursor_style: CursorStyle,
 
@@ -351,17 +375,23 @@ pub struct Config {
 
     /// OSC52 support mode.
     pub osc52: Osc52,
+
+    /// Window operations applications are allowed to use.
+    pub window_ops: WindowOps,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             scrolling_history: 10000,
+            compressed_history: 0,
+            collect_evicted_lines: false,
             semantic_escape_chars: SEMANTIC_ESCAPE_CHARS.to_owned(),
             default_cursor_style: Default::default(),
             vi_mode_cursor_style: Default::default(),
             kitty_keyboard: Default::default(),
             osc52: Default::default(),
+            window_ops: Default::default(),
         }
     }
 }
@@ -391,9 +421,26 @@ impl<T> Term<T> {
         T: EventListener,
     {
         let old_display_offset = self.grid.display_offset();
+
+        // Restore compressed lines when scrolling beyond the top of the history.
+        let target = match scroll {
+            Scroll::Delta(count) if count > 0 => old_display_offset + count as usize,
+            Scroll::PageUp => old_display_offset + self.screen_lines(),
+            Scroll::Top => usize::MAX,
+            _ => 0,
+        };
+        if let Some(missing) = target.checked_sub(self.grid.history_size()).filter(|&m| m > 0) {
+            self.grid.thaw_history(missing);
+        }
+
         self.grid.scroll_display(scroll);
         self.event_proxy.send_event(Event::MouseCursorDirty);
 
+        // Compress restored history again once it's out of view.
+        if self.grid.display_offset() == 0 {
+            self.freeze_history();
+        }
+
         // Clamp vi mode cursor to the viewport.
         let viewport_start = -(self.grid.display_offset() as i32);
         let viewport_end = viewport_start + self.bottommost_line().0;
@@ -407,12 +454,39 @@ impl<T> Term<T> {
         }
     }
 
+    /// Compress lines restored from the compressed history.
+    ///
+    /// This is done automatically when scrolling back to the bottom, but might be necessary after
+    /// restoring lines manually using [`Grid::thaw_history`].
+    pub fn freeze_history(&mut self) {
+        let old_topmost_line = self.grid.topmost_line();
+        if self.grid.freeze_history() == 0 {
+            return;
+        }
+
+        // Clear selections which are no longer part of the grid.
+        let removed_lines = old_topmost_line..self.grid.topmost_line();
+        if self.selection.as_ref().is_some_and(|sel| sel.intersects_range(removed_lines)) {
+            self.selection = None;
+        }
+
+        // Move vi cursor out of the removed lines.
+        let topmost_line = self.grid.topmost_line();
+        if self.vi_mode_cursor.point.line < topmost_line {
+            self.vi_mode_cursor.point = Point::new(topmost_line, Column(0));
+        }
+
+        self.mark_fully_damaged();
+    }
+
     pub fn new<D: Dimensions>(config: Config, dimensions: &D, event_proxy: T) -> Term<T> {
         let num_cols = dimensions.columns();
         let num_lines = dimensions.screen_lines();
 
         let history_size = config.scrolling_history;
-        let grid = Grid::new(num_lines, num_cols, history_size);
+        let mut grid = Grid::new(num_lines, num_cols, history_size);
+        grid.update_compressed_history(config.compressed_history);
+        grid.set_collect_evicted(config.collect_evicted_lines);
         let inactive_grid = Grid::new(num_lines, num_cols, 0);
 
         let tabs = TabStops::new(grid.columns());
@@ -438,6 +512,8 @@ impl<T> Term<T> {
             colors: color::Colors::default(),
             title_stack: Default::default(),
             is_focused: Default::default(),
+            last_output: Default::default(),
+            working_directory: Default::default(),
             selection: Default::default(),
             title: Default::default(),
             mode: Default::default(),
@@ -509,10 +585,18 @@ impl<T> Term<T> {
 
         self.event_proxy.send_event(title_event);
 
+        let compressed_history = self.config.compressed_history;
+        let collect_evicted = self.config.collect_evicted_lines;
         if self.mode.contains(TermMode::ALT_SCREEN) {
+            self.inactive_grid.freeze_history();
             self.inactive_grid.update_history(self.config.scrolling_history);
+            self.inactive_grid.update_compressed_history(compressed_history);
+            self.inactive_grid.set_collect_evicted(collect_evicted);
         } else {
+            self.freeze_history();
             self.grid.update_history(self.config.scrolling_history);
+            self.grid.update_compressed_history(compressed_history);
+            self.grid.set_collect_evicted(collect_evicted);
         }
 
         if self.config.kitty_keyboard != old_config.kitty_keyboard {
@@ -543,7 +627,7 @@ impl<T> Term<T> {
 
                 res += self.line_to_string(end.line, start.column..end.column, true).trim_end();
             },
-            Some(Selection { ty: SelectionType::Lines, .. }) => {
+            Some(Selection { ty: SelectionType::Lines | SelectionType::Rows, .. }) => {
                 res = self.bounds_to_string(start, end) + "\n";
             },
             _ => {
@@ -568,6 +652,30 @@ impl<T> Term<T> {
         res.strip_suffix('\n').map(str::to_owned).unwrap_or(res)
     }
 
+    /// Time of the last output read from the PTY.
+    #[inline]
+    pub fn last_output(&self) -> Option<Instant> {
+        self.last_output
+    }
+
+    /// Record that output was read from the PTY.
+    #[inline]
+    pub fn record_output(&mut self) {
+        self.last_output = Some(Instant::now());
+    }
+
+    /// Working directory reported by the shell through OSC 7.
+    #[inline]
+    pub fn working_directory(&self) -> Option<&WorkingDirectory> {
+        self.working_directory.as_ref()
+    }
+
+    /// Set the working directory reported by the shell.
+    #[inline]
+    pub fn set_working_directory(&mut self, working_directory: Option<WorkingDirectory>) {
+        self.working_directory = working_directory;
+    }
+
     /// Convert a single line in the grid to a String.
     fn line_to_string(
         &self,
@@ -585,13 +693,59 @@ impl<T> Term<T> {
             cols.start -= 1;
         }
 
+        self.push_row_text(&mut text, grid_line, cols.start..line_length);
+
+        if cols.end >= self.columns() - 1
+            && (line_length.0 == 0
+                || !self.grid[line][line_length - 1].flags.contains(Flags::WRAPLINE))
+        {
+            text.push('\n');
+        }
+
+        // If wide char is not part of the selection, but leading spacer is, include it.
+        if line_length == self.columns()
+            && line_length.0 >= 2
+            && grid_line[line_length - 1].flags.contains(Flags::LEADING_WIDE_CHAR_SPACER)
+            && include_wrapped_wide
+        {
+            text.push(self.grid[line - 1i32][Column(0)].c);
+        }
+
+        text
+    }
+
+    /// Take the text of all lines rotated out of the scrolling history since the last call.
+    ///
+    /// Lines are only collected when [`Config::collect_evicted_lines`] is enabled. Every line
+    /// ends with a newline, unless it was wrapped into the next one.
+    pub fn take_evicted_text(&mut self) -> String {
+        let mut rows = self.inactive_grid.take_evicted();
+        rows.append(&mut self.grid.take_evicted());
+
+        let mut text = String::new();
+        for row in &rows {
+            let line_length = row.line_length();
+            self.push_row_text(&mut text, row, Column(0)..line_length);
+
+            if line_length.0 == 0 || !row[line_length - 1].flags.contains(Flags::WRAPLINE) {
+                text.push('\n');
+            }
+        }
+
+        text
+    }
+
+    /// Append the text of a row's cells, skipping tab padding and wide char spacers.
+    fn push_row_text(&self, text: &mut String, row: &Row<Cell>, cols: Range<Column>) {
         let mut tab_mode = false;
-        for column in (cols.start.0..line_length.0).map(Column::from) {
-            let cell = &grid_line[column];
+        for column in (cols.start.0..cols.end.0).map(Column::from) {
+            let cell = &row[column];
 
             // Skip over cells until next tab-stop once a tab was found.
+            //
+            // Rows rotated out of the history might be wider than the current tab stops.
             if tab_mode {
-                if self.tabs[column] || cell.c != ' ' {
+                if column >= self.columns() || self.tabs[column] || cell.c != ' ' {
                     tab_mode = false;
                 } else {
                     continue;
@@ -612,24 +766,6 @@ impl<T> Term<T> {
                 }
             }
         }
-
-        if cols.end >= self.columns() - 1
-            && (line_length.0 == 0
-                || !self.grid[line][line_length - 1].flags.contains(Flags::WRAPLINE))
-        {
-            text.push('\n');
-        }
-
-        // If wide char is not part of the selection, but leading spacer is, include it.
-        if line_length == self.columns()
-            && line_length.0 >= 2
-            && grid_line[line_length - 1].flags.contains(Flags::LEADING_WIDE_CHAR_SPACER)
-            && include_wrapped_wide
-        {
-            text.push(self.grid[line - 1i32][Column(0)].c);
-        }
-
-        text
     }
 
     /// Terminal content required for rendering.
@@ -1039,6 +1175,31 @@ impl<T> Term<T> {
     }
 }
 
+impl<T: EventListener> Term<T> {
+    /// Reset the terminal modes, keeping the content of the primary screen.
+    ///
+    /// This restores the input and cursor modes left behind by programs which were killed before
+    /// they could reset them.
+    pub fn reset_modes(&mut self) {
+        if self.mode.contains(TermMode::ALT_SCREEN) {
+            self.swap_alt();
+        }
+
+        self.keyboard_mode_stack = Vec::new();
+        self.inactive_keyboard_mode_stack = Vec::new();
+        self.scroll_region = Line(0)..Line(self.screen_lines() as i32);
+        self.cursor_style = None;
+
+        // Preserve vi mode across resets.
+        self.mode &= TermMode::VI;
+        self.mode.insert(TermMode::default());
+
+        self.event_proxy.send_event(Event::MouseCursorDirty);
+        self.event_proxy.send_event(Event::CursorBlinkingChange);
+        self.mark_fully_damaged();
+    }
+}
+
 impl<T> Dimensions for Term<T> {
     #[inline]
     fn columns(&self) -> usize {
@@ -2419,6 +2580,8 @@ pub mod test {
     #[cfg(feature = "serde")]
     use serde::{Deserialize, Serialize};
 
+    use std::sync::Mutex;
+
     use crate::event::VoidListener;
 
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -2447,6 +2610,23 @@ pub mod test {
         }
     }
 
+    /// Event listener recording all events sent by the terminal.
+    #[derive(Default, Clone)]
+    pub struct RecordingListener(Arc<Mutex<Vec<Event>>>);
+
+    impl RecordingListener {
+        /// Take all events recorded since the last call.
+        pub fn take_events(&self) -> Vec<Event> {
+            mem::take(&mut *self.0.lock().unwrap())
+        }
+    }
+
+    impl EventListener for RecordingListener {
+        fn send_event(&self, event: Event) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
     /// Construct a terminal from its content as string.
     ///
     /// A `\n` will break line and `\r\n` will break line without wrapping.
@@ -2873,6 +3053,81 @@ mod tests {
         assert_eq!(term.grid, scrolled_grid);
     }
 
+    #[test]
+    fn take_evicted_text() {
+        let size = TermSize::new(5, 2);
+        let config =
+            Config { scrolling_history: 1, collect_evicted_lines: true, ..Default::default() };
+        let mut term = Term::new(config, &size, VoidListener);
+
+        // Write four lines, with the second one wrapping into the third.
+        for c in "a\r\nbcdefgh\r\ni\r\n".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+
+        // Only the oldest lines exceed the single line of history.
+        assert_eq!(term.take_evicted_text(), "a\nbcdef");
+        assert_eq!(term.take_evicted_text(), "");
+
+        term.linefeed();
+        assert_eq!(term.take_evicted_text(), "gh\n");
+    }
+
+    #[test]
+    fn scroll_compressed_history() {
+        let size = TermSize::new(5, 2);
+        let config =
+            Config { scrolling_history: 1, compressed_history: 1024, ..Default::default() };
+        let mut term = Term::new(config, &size, VoidListener);
+
+        for c in "a\r\nb\r\nc\r\nd".chars() {
+            match c {
+                '\r' => term.carriage_return(),
+                '\n' => term.linefeed(),
+                c => term.input(c),
+            }
+        }
+        assert_eq!(term.grid.compressed_lines(), 1);
+
+        // Scrolling beyond the top of the history restores compressed lines.
+        term.scroll_display(Scroll::Top);
+        assert_eq!(term.grid.compressed_lines(), 0);
+        assert_eq!(term.grid.display_offset(), 2);
+        assert_eq!(term.grid[Line(-2)][Column(0)].c, 'a');
+
+        // Returning to the bottom compresses them again.
+        term.scroll_display(Scroll::Bottom);
+        assert_eq!(term.grid.compressed_lines(), 1);
+        assert_eq!(term.grid.history_size(), 1);
+    }
+
+    #[test]
+    fn reset_modes() {
+        let size = TermSize::new(5, 5);
+        let mut term = Term::new(Config::default(), &size, VoidListener);
+
+        term.input('a');
+        term.set_private_mode(NamedPrivateMode::ReportAllMouseMotion.into());
+        term.set_private_mode(NamedPrivateMode::BracketedPaste.into());
+        term.set_private_mode(NamedPrivateMode::CursorKeys.into());
+        term.unset_private_mode(NamedPrivateMode::ShowCursor.into());
+        term.set_keypad_application_mode();
+        term.push_keyboard_mode(KeyboardModes::DISAMBIGUATE_ESC_CODES);
+        term.set_private_mode(NamedPrivateMode::SwapScreenAndSetRestoreCursor.into());
+        term.set_scrolling_region(2, Some(3));
+
+        term.reset_modes();
+
+        assert_eq!(*term.mode(), TermMode::default());
+        assert!(term.keyboard_mode_stack.is_empty());
+        assert_eq!(term.scroll_region, Line(0)..Line(5));
+        assert_eq!(term.grid[Line(0)][Column(0)].c, 'a');
+    }
+
     #[test]
     fn vi_cursor_keep_pos_on_scrollback_buffer() {
         let size = TermSize::new(5, 10);
@@ -0,0 +1,332 @@
+//! Escape sequences handled outside of the ANSI parser.
+//!
+//! The ANSI parser ignores OSC 7 and device control strings and only handles some window
+//! operations, so these sequences are extracted from the PTY output by the [`SequenceScanner`]
+//! and handled by the [`Term`] once all preceding bytes have been parsed.
+
+use std::mem;
+
+use log::debug;
+
+use crate::event::EventListener;
+use crate::term::Term;
+use crate::term::dcs::DcsQuery;
+use crate::term::window_ops::WindowOp;
+use crate::term::working_directory::WorkingDirectory;
+
+/// Maximum number of bytes in a string's payload.
+const MAX_STRING_LEN: usize = 4096;
+
+/// Maximum number of parameters of a window operation.
+const MAX_PARAMS: usize = 3;
+
+/// First parameters of the window operations handled by the ANSI parser.
+const PARSER_OPS: [u16; 4] = [14, 18, 22, 23];
+
+/// Escape sequence ignored by the ANSI parser.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sequence {
+    /// Working directory reported through OSC 7.
+    WorkingDirectory(WorkingDirectory),
+
+    /// Device control string query.
+    DcsQuery(DcsQuery),
+
+    /// Xterm window operation of `CSI Ps ; Ps ; Ps t`.
+    WindowOp(WindowOp),
+}
+
+/// Scanner extracting sequences ignored by the ANSI parser from PTY output.
+#[derive(Debug, Default)]
+pub struct SequenceScanner {
+    state: State,
+    string: StringKind,
+    intermediate: Option<u8>,
+    data: Vec<u8>,
+    params: [u16; MAX_PARAMS],
+    param_count: usize,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+enum State {
+    #[default]
+    Ground,
+    Escape,
+    OscCommand,
+    DcsEntry,
+    Data,
+    DataEscape,
+    Ignore,
+    IgnoreEscape,
+    CsiParams,
+    CsiIgnore,
+}
+
+/// Kind of the string being scanned.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+enum StringKind {
+    #[default]
+    Osc,
+    Dcs,
+}
+
+impl SequenceScanner {
+    /// Scan bytes for the next complete sequence.
+    ///
+    /// Returns the sequence with the number of bytes up to and including its last byte. The
+    /// scanner keeps its state, so the remaining bytes should be passed to the next call.
+    pub fn advance(&mut self, bytes: &[u8]) -> Option<(usize, Sequence)> {
+        let mut offset = 0;
+        while offset < bytes.len() {
+            // Skip ahead to the next escape.
+            if self.state == State::Ground {
+                let escape = bytes[offset..].iter().position(|&byte| byte == 0x1b)?;
+                offset += escape + 1;
+                self.state = State::Escape;
+                continue;
+            }
+
+            let byte = bytes[offset];
+            offset += 1;
+
+            if let Some(sequence) = self.advance_byte(byte) {
+                return Some((offset, sequence));
+            }
+        }
+
+        None
+    }
+
+    fn advance_byte(&mut self, byte: u8) -> Option<Sequence> {
+        // Cancel the sequence on CAN and SUB.
+        if matches!(byte, 0x18 | 0x1a) {
+            self.state = State::Ground;
+            return None;
+        }
+
+        match (self.state, byte) {
+            (State::Ground, 0x1b) => self.state = State::Escape,
+            (State::Ground, _) => (),
+            (State::Escape, b']') => {
+                self.string = StringKind::Osc;
+                self.data.clear();
+                self.state = State::OscCommand;
+            },
+            (State::Escape, b'P') => {
+                self.string = StringKind::Dcs;
+                self.intermediate = None;
+                self.data.clear();
+                self.state = State::DcsEntry;
+            },
+            (State::Escape, b'[') => {
+                self.params = [0; MAX_PARAMS];
+                self.param_count = 1;
+                self.state = State::CsiParams;
+            },
+            (State::Escape, 0x1b) => (),
+            (State::Escape, _) => self.state = State::Ground,
+            // Only OSC 7 is collected.
+            (State::OscCommand, b'0'..=b'9') if self.data.len() < 3 => self.data.push(byte),
+            (State::OscCommand, b';') if self.data == b"7" => {
+                self.data.clear();
+                self.state = State::Data;
+            },
+            // Queries have a single intermediate and no parameters.
+            (State::DcsEntry, 0x20..=0x2f) if self.intermediate.is_none() => {
+                self.intermediate = Some(byte);
+            },
+            (State::DcsEntry, b'q') if matches!(self.intermediate, Some(b'+' | b'$')) => {
+                self.state = State::Data;
+            },
+            (State::OscCommand | State::DcsEntry | State::Ignore, 0x1b) => {
+                self.state = State::IgnoreEscape;
+            },
+            (State::OscCommand | State::Ignore, 0x07) if self.string == StringKind::Osc => {
+                self.state = State::Ground;
+            },
+            (State::OscCommand | State::DcsEntry, _) => self.state = State::Ignore,
+            (State::Data, 0x07) if self.string == StringKind::Osc => {
+                self.state = State::Ground;
+                return self.finish_string();
+            },
+            (State::Data, 0x1b) => self.state = State::DataEscape,
+            (State::Data, _) if self.data.len() >= MAX_STRING_LEN => self.state = State::Ignore,
+            (State::Data, _) => self.data.push(byte),
+            (State::DataEscape, b'\\') => {
+                self.state = State::Ground;
+                return self.finish_string();
+            },
+            (State::Ignore, _) => (),
+            (State::IgnoreEscape, b'\\') => self.state = State::Ground,
+            // An escape without string terminator starts a new sequence.
+            (State::DataEscape | State::IgnoreEscape, _) => {
+                self.state = State::Escape;
+                return self.advance_byte(byte);
+            },
+            (State::CsiParams | State::CsiIgnore, 0x1b) => self.state = State::Escape,
+            // Control characters are executed within the sequence.
+            (State::CsiParams | State::CsiIgnore, 0x00..=0x1f) => (),
+            (State::CsiParams, b'0'..=b'9') => {
+                let param = &mut self.params[self.param_count - 1];
+                *param = param.saturating_mul(10).saturating_add((byte - b'0').into());
+            },
+            (State::CsiParams, b';') if self.param_count < MAX_PARAMS => self.param_count += 1,
+            (State::CsiParams, b't') => {
+                self.state = State::Ground;
+                return self.window_op();
+            },
+            // Leave private and intermediate sequences to the parser.
+            (State::CsiParams | State::CsiIgnore, 0x40..=0x7e) => self.state = State::Ground,
+            (State::CsiParams | State::CsiIgnore, _) => self.state = State::CsiIgnore,
+        }
+
+        None
+    }
+
+    /// Parse the collected string.
+    fn finish_string(&mut self) -> Option<Sequence> {
+        let data = mem::take(&mut self.data);
+        match (self.string, self.intermediate) {
+            (StringKind::Osc, _) => {
+                let directory = WorkingDirectory::from_uri(&data);
+                if directory.is_none() {
+                    debug!("Invalid working directory {:?}", String::from_utf8_lossy(&data));
+                }
+                directory.map(Sequence::WorkingDirectory)
+            },
+            (StringKind::Dcs, Some(b'+')) => Some(Sequence::DcsQuery(DcsQuery::Termcap(data))),
+            (StringKind::Dcs, _) => Some(Sequence::DcsQuery(DcsQuery::StatusString(data))),
+        }
+    }
+
+    /// Parse the collected window operation.
+    fn window_op(&self) -> Option<Sequence> {
+        let params = &self.params[..self.param_count];
+        if PARSER_OPS.contains(&params[0]) {
+            return None;
+        }
+
+        let op = WindowOp::from_params(params);
+        if op.is_none() {
+            debug!("Unsupported window operation {params:?}");
+        }
+        op.map(Sequence::WindowOp)
+    }
+}
+
+impl<T: EventListener> Term<T> {
+    /// Handle a sequence ignored by the ANSI parser.
+    pub fn handle_sequence(&mut self, sequence: Sequence) {
+        match sequence {
+            Sequence::WorkingDirectory(directory) => self.set_working_directory(Some(directory)),
+            Sequence::DcsQuery(query) => self.dcs_query(query),
+            Sequence::WindowOp(op) => self.window_op(op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory(host: &str, path: &str) -> Option<Sequence> {
+        let directory = WorkingDirectory { host: host.into(), path: path.into() };
+        Some(Sequence::WorkingDirectory(directory))
+    }
+
+    fn query(query: DcsQuery) -> Option<Sequence> {
+        Some(Sequence::DcsQuery(query))
+    }
+
+    fn window_op(op: WindowOp) -> Option<Sequence> {
+        Some(Sequence::WindowOp(op))
+    }
+
+    fn advance(scanner: &mut SequenceScanner, bytes: &[u8]) -> (Option<usize>, Option<Sequence>) {
+        match scanner.advance(bytes) {
+            Some((end, sequence)) => (Some(end), Some(sequence)),
+            None => (None, None),
+        }
+    }
+
+    #[test]
+    fn scan_working_directories() {
+        let mut scanner = SequenceScanner::default();
+
+        let bytes = b"a\x1b]7;file://box/tmp\x07b\x1b]7;file:///home/a%20b\x1b\\\x1b]0;title\x07";
+        assert_eq!(advance(&mut scanner, bytes), (Some(20), directory("box", "/tmp")));
+        assert_eq!(advance(&mut scanner, &bytes[20..]), (Some(25), directory("", "/home/a b")));
+        assert_eq!(scanner.advance(&bytes[45..]), None);
+
+        // Reports split across multiple reads.
+        assert_eq!(scanner.advance(b"\x1b]7;file://box/s"), None);
+        assert_eq!(scanner.advance(b"rc\x1b"), None);
+        assert_eq!(advance(&mut scanner, b"\\"), (Some(1), directory("box", "/src")));
+
+        // Invalid reports are skipped.
+        let bytes = b"\x1b]7;http://box/\x07\x1b]77;file://box/\x07\x1b]7;file://box/%zz\x07";
+        assert_eq!(scanner.advance(bytes), None);
+
+        // Escapes abort the report.
+        let bytes = b"\x1b]7;file://a/\x1b[m\x1b]7;file://b/\x07";
+        assert_eq!(advance(&mut scanner, bytes), (Some(30), directory("b", "/")));
+    }
+
+    #[test]
+    fn scan_queries() {
+        let mut scanner = SequenceScanner::default();
+
+        let bytes = b"a\x1bP+q544E\x1b\\b\x1bP$qm\x1b\\";
+        let termcap = query(DcsQuery::Termcap(b"544E".to_vec()));
+        assert_eq!(advance(&mut scanner, bytes), (Some(11), termcap));
+        let status = query(DcsQuery::StatusString(b"m".to_vec()));
+        assert_eq!(advance(&mut scanner, &bytes[11..]), (Some(8), status));
+        assert_eq!(scanner.advance(&bytes[19..]), None);
+
+        // Queries split across multiple reads.
+        assert_eq!(scanner.advance(b"\x1bP$q r\x1b"), None);
+        let status = query(DcsQuery::StatusString(b" r".to_vec()));
+        assert_eq!(advance(&mut scanner, b"\\"), (Some(1), status));
+
+        // Other device control strings are ignored, even when terminated by BEL.
+        assert_eq!(scanner.advance(b"\x1bP=1s\x1b\\\x1bP1$qm\x1b\\\x1bPq\x07\x1b]7;\x1b\\"), None);
+
+        // Escapes abort the query.
+        assert_eq!(advance(&mut scanner, b"\x1bP$qm\x1b[m\x1bP$qr\x1b\\").0, Some(15));
+    }
+
+    #[test]
+    fn scan_window_ops() {
+        let mut scanner = SequenceScanner::default();
+
+        let bytes = b"a\x1b[16tb\x1b[3;10;20t\x1b[14t\x1b[m";
+        assert_eq!(advance(&mut scanner, bytes), (Some(6), window_op(WindowOp::ReportCellSize)));
+        let move_op = window_op(WindowOp::Move { x: 10, y: 20 });
+        assert_eq!(advance(&mut scanner, &bytes[6..]), (Some(11), move_op));
+
+        // Operations handled by the parser and other sequences are ignored.
+        assert_eq!(scanner.advance(&bytes[17..]), None);
+
+        // Operations split across multiple reads.
+        assert_eq!(scanner.advance(b"\x1b[8;24"), None);
+        let resize = window_op(WindowOp::ResizeCells { columns: Some(80), lines: Some(24) });
+        assert_eq!(advance(&mut scanner, b";80t"), (Some(4), resize));
+
+        // Private sequences and escapes abort the operation.
+        let bytes = b"\x1b[>0t\x1b[1\x1b[2t";
+        assert_eq!(advance(&mut scanner, bytes), (Some(12), window_op(WindowOp::Iconify(true))));
+    }
+
+    #[test]
+    fn scan_mixed_sequences() {
+        let mut scanner = SequenceScanner::default();
+
+        // Sequences are returned in order, with strings terminated by their own rules.
+        let bytes = b"\x1b[11t\x1bP$q\x07m\x1b\\\x1b]7;file://a/\x07";
+        assert_eq!(advance(&mut scanner, bytes), (Some(5), window_op(WindowOp::ReportState)));
+        let status = query(DcsQuery::StatusString(b"\x07m".to_vec()));
+        assert_eq!(advance(&mut scanner, &bytes[5..]), (Some(8), status));
+        assert_eq!(advance(&mut scanner, &bytes[13..]), (Some(14), directory("a", "/")));
+        assert_eq!(scanner.advance(&bytes[27..]), None);
+    }
+}
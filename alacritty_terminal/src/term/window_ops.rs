@@ -0,0 +1,279 @@
+//! Xterm window operations.
+//!
+//! The ANSI parser only handles the title stack and text area size reports of `CSI Ps ; Ps ; Ps t`,
+//! so the remaining window operations are extracted from the PTY output by the
+//! [`SequenceScanner`] and handled by the [`Term`] once all preceding bytes have been parsed.
+//!
+//! [`SequenceScanner`]: crate::term::sequence::SequenceScanner
+
+use std::sync::Arc;
+
+use log::{debug, trace};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::event::{Event, EventListener};
+use crate::term::Term;
+
+/// Window operation requested by an application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowOp {
+    /// Minimize (`true`) or restore (`false`) the window.
+    Iconify(bool),
+
+    /// Move the window's top-left corner to a position in pixels.
+    Move { x: i32, y: i32 },
+
+    /// Resize the text area in pixels, keeping dimensions which are `None`.
+    ResizePixels { width: Option<u32>, height: Option<u32> },
+
+    /// Resize the text area in cells, keeping dimensions which are `None`.
+    ResizeCells { columns: Option<usize>, lines: Option<usize> },
+
+    /// Raise the window to the front of the stacking order.
+    Raise,
+
+    /// Lower the window to the bottom of the stacking order.
+    Lower,
+
+    /// Redraw the window.
+    Refresh,
+
+    /// Maximize (`true`) or restore (`false`) the window.
+    Maximize(bool),
+
+    /// Enter (`Some(true)`), leave (`Some(false)`) or toggle (`None`) fullscreen.
+    Fullscreen(Option<bool>),
+
+    /// Report whether the window is minimized.
+    ReportState,
+
+    /// Report the window position in pixels.
+    ReportPosition,
+
+    /// Report the screen size in pixels.
+    ReportScreenPixels,
+
+    /// Report the cell size in pixels.
+    ReportCellSize,
+
+    /// Report the screen size in cells.
+    ReportScreenCells,
+
+    /// Report the icon label.
+    ReportIconLabel,
+
+    /// Report the window title.
+    ReportTitle,
+}
+
+impl WindowOp {
+    /// Window operation of `CSI t` parameters.
+    pub fn from_params(params: &[u16]) -> Option<Self> {
+        let param = |index: usize| params.get(index).copied().unwrap_or_default();
+        let size = |index: usize| Some(param(index)).filter(|&size| size != 0);
+
+        let op = match param(0) {
+            // The first parameter defaults to de-iconifying the window.
+            0 | 1 => Self::Iconify(false),
+            2 => Self::Iconify(true),
+            3 => Self::Move { x: param(1).into(), y: param(2).into() },
+            4 => Self::ResizePixels {
+                width: size(2).map(Into::into),
+                height: size(1).map(Into::into),
+            },
+            5 => Self::Raise,
+            6 => Self::Lower,
+            7 => Self::Refresh,
+            8 => Self::ResizeCells {
+                columns: size(2).map(Into::into),
+                lines: size(1).map(Into::into),
+            },
+            9 => Self::Maximize(param(1) != 0),
+            10 => match param(1) {
+                0 => Self::Fullscreen(Some(false)),
+                1 => Self::Fullscreen(Some(true)),
+                _ => Self::Fullscreen(None),
+            },
+            11 => Self::ReportState,
+            13 => Self::ReportPosition,
+            15 => Self::ReportScreenPixels,
+            16 => Self::ReportCellSize,
+            19 => Self::ReportScreenCells,
+            20 => Self::ReportIconLabel,
+            21 => Self::ReportTitle,
+            // DECSLPP, resize to the number of lines.
+            lines @ 24.. => Self::ResizeCells { columns: None, lines: Some(lines.into()) },
+            _ => return None,
+        };
+
+        Some(op)
+    }
+}
+
+/// Window operations applications are allowed to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct WindowOps {
+    /// Report the window state, position, cell size and screen size.
+    pub report: bool,
+
+    /// Report the window title and icon label.
+    pub report_title: bool,
+
+    /// Resize, maximize and fullscreen the window.
+    pub resize: bool,
+
+    /// Move the window.
+    pub position: bool,
+
+    /// Minimize, restore, raise and lower the window.
+    pub stacking: bool,
+}
+
+impl Default for WindowOps {
+    fn default() -> Self {
+        Self { report: true, report_title: false, resize: false, position: false, stacking: false }
+    }
+}
+
+impl WindowOps {
+    /// Check whether an operation is allowed.
+    pub fn allows(&self, op: WindowOp) -> bool {
+        match op {
+            WindowOp::Refresh => true,
+            WindowOp::ReportState
+            | WindowOp::ReportPosition
+            | WindowOp::ReportScreenPixels
+            | WindowOp::ReportCellSize
+            | WindowOp::ReportScreenCells => self.report,
+            WindowOp::ReportIconLabel | WindowOp::ReportTitle => self.report_title,
+            WindowOp::ResizePixels { .. }
+            | WindowOp::ResizeCells { .. }
+            | WindowOp::Maximize(_)
+            | WindowOp::Fullscreen(_) => self.resize,
+            WindowOp::Move { .. } => self.position,
+            WindowOp::Iconify(_) | WindowOp::Raise | WindowOp::Lower => self.stacking,
+        }
+    }
+}
+
+/// Window information reported to applications.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WindowState {
+    /// Whether the window is minimized.
+    pub iconified: bool,
+
+    /// Position of the window's top-left corner in pixels.
+    pub x: i32,
+    pub y: i32,
+
+    /// Size of the screen containing the window in pixels.
+    pub screen_width: u32,
+    pub screen_height: u32,
+
+    /// Size of a cell in pixels.
+    pub cell_width: u32,
+    pub cell_height: u32,
+}
+
+impl<T: EventListener> Term<T> {
+    /// Handle a window operation allowed by [`Config::window_ops`].
+    ///
+    /// [`Config::window_ops`]: crate::term::Config::window_ops
+    pub fn window_op(&mut self, op: WindowOp) {
+        if !self.config.window_ops.allows(op) {
+            debug!("Denied window operation {op:?}");
+            return;
+        }
+
+        trace!("Handling window operation {op:?}");
+
+        let format: fn(WindowState) -> String = match op {
+            WindowOp::ReportState => |state| format!("\x1b[{}t", 1 + u8::from(state.iconified)),
+            WindowOp::ReportPosition => |state| format!("\x1b[3;{};{}t", state.x, state.y),
+            WindowOp::ReportScreenPixels => {
+                |state| format!("\x1b[5;{};{}t", state.screen_height, state.screen_width)
+            },
+            WindowOp::ReportCellSize => {
+                |state| format!("\x1b[6;{};{}t", state.cell_height, state.cell_width)
+            },
+            WindowOp::ReportScreenCells => |state| {
+                let lines = state.screen_height / state.cell_height.max(1);
+                let columns = state.screen_width / state.cell_width.max(1);
+                format!("\x1b[9;{lines};{columns}t")
+            },
+            WindowOp::ReportIconLabel => return self.report_title('L'),
+            WindowOp::ReportTitle => return self.report_title('l'),
+            _ => return self.event_proxy.send_event(Event::WindowOp(op)),
+        };
+
+        self.event_proxy.send_event(Event::WindowStateRequest(Arc::new(format)));
+    }
+
+    /// Report the title as window title (`l`) or icon label (`L`).
+    fn report_title(&mut self, kind: char) {
+        // Strip control characters to prevent titles from injecting input.
+        let title: String =
+            self.title.iter().flat_map(|title| title.chars()).filter(|c| !c.is_control()).collect();
+        self.event_proxy.send_event(Event::PtyWrite(format!("\x1b]{kind}{title}\x1b\\")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::term::Config;
+    use crate::term::test::{RecordingListener, TermSize};
+    use crate::vte::ansi::Handler;
+
+    #[test]
+    fn window_op_params() {
+        assert_eq!(WindowOp::from_params(&[0]), Some(WindowOp::Iconify(false)));
+        assert_eq!(
+            WindowOp::from_params(&[4, 0, 640]),
+            Some(WindowOp::ResizePixels { width: Some(640), height: None })
+        );
+        assert_eq!(WindowOp::from_params(&[10, 2]), Some(WindowOp::Fullscreen(None)));
+        assert_eq!(
+            WindowOp::from_params(&[48]),
+            Some(WindowOp::ResizeCells { columns: None, lines: Some(48) })
+        );
+        assert_eq!(WindowOp::from_params(&[12]), None);
+    }
+
+    #[test]
+    fn window_ops_policy() {
+        let listener = RecordingListener::default();
+        let size = TermSize::new(10, 5);
+        let mut term = Term::new(Config::default(), &size, listener.clone());
+
+        // Only reports are allowed by default.
+        term.window_op(WindowOp::Move { x: 0, y: 0 });
+        term.window_op(WindowOp::ReportTitle);
+        assert!(listener.take_events().is_empty());
+
+        term.window_op(WindowOp::ReportCellSize);
+        let events = listener.take_events();
+        let state = WindowState { cell_width: 7, cell_height: 15, ..Default::default() };
+        match events.as_slice() {
+            [Event::WindowStateRequest(format)] => assert_eq!(format(state), "\x1b[6;15;7t"),
+            events => panic!("unexpected events {events:?}"),
+        }
+
+        let config = Config {
+            window_ops: WindowOps { report_title: true, ..Default::default() },
+            ..Default::default()
+        };
+        term.set_options(config);
+        term.set_title(Some("vim\x1b[1m".into()));
+        listener.take_events();
+
+        term.window_op(WindowOp::ReportTitle);
+        match listener.take_events().as_slice() {
+            [Event::PtyWrite(text)] => assert_eq!(text, "\x1b]lvim[1m\x1b\\"),
+            events => panic!("unexpected events {events:?}"),
+        }
+    }
+}
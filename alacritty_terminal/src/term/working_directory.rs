@@ -0,0 +1,75 @@
+//! Working directory reported by the shell through OSC 7.
+//!
+//! The ANSI parser ignores OSC 7, so reports are extracted from the PTY output by the
+//! [`SequenceScanner`].
+//!
+//! [`SequenceScanner`]: crate::term::sequence::SequenceScanner
+
+use std::path::PathBuf;
+
+/// URI schemes of working directory reports.
+const SCHEMES: [&str; 2] = ["file://", "kitty-shell-cwd://"];
+
+/// Working directory reported by the shell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkingDirectory {
+    /// Host of the directory, which is empty for the local host.
+    pub host: String,
+    pub path: PathBuf,
+}
+
+impl WorkingDirectory {
+    /// Parse a `file://host/path` URI.
+    pub fn from_uri(uri: &[u8]) -> Option<Self> {
+        let uri = std::str::from_utf8(uri).ok()?;
+        let uri = SCHEMES.iter().find_map(|scheme| uri.strip_prefix(scheme))?;
+
+        let (host, path) = uri.split_at(uri.find('/')?);
+        if host.chars().any(char::is_control) {
+            return None;
+        }
+
+        let path = String::from_utf8(percent_decode(path)?).ok()?;
+        Some(Self { host: host.into(), path: path.into() })
+    }
+}
+
+/// Decode percent-encoded bytes.
+fn percent_decode(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len());
+
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn directory(host: &str, path: &str) -> WorkingDirectory {
+        WorkingDirectory { host: host.into(), path: path.into() }
+    }
+
+    #[test]
+    fn parse_uris() {
+        assert_eq!(WorkingDirectory::from_uri(b"file://box/a"), Some(directory("box", "/a")));
+        assert_eq!(
+            WorkingDirectory::from_uri(b"kitty-shell-cwd://box/a"),
+            Some(directory("box", "/a"))
+        );
+        assert_eq!(WorkingDirectory::from_uri(b"file://box"), None);
+        assert_eq!(WorkingDirectory::from_uri(b"file://b\x1bx/"), None);
+        assert_eq!(WorkingDirectory::from_uri(b"file://box/%4"), None);
+    }
+}
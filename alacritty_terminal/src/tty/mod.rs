@@ -93,6 +93,19 @@ pub trait EventedPty: EventedReadWrite {
     ///
     /// Returns `Some(event)` on success, or `None` if there are no events to retrieve.
     fn next_child_event(&mut self) -> Option<ChildEvent>;
+
+    /// Check whether the PTY is driven by a child process.
+    ///
+    /// Without a child process, no [`ChildEvent`] is emitted and the PTY hanging up terminates
+    /// the terminal instead.
+    fn has_child(&self) -> bool {
+        true
+    }
+
+    /// Send a signal to the foreground process group of the PTY.
+    fn signal_foreground(&self, _signal: i32) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
 }
 
 /// Setup environment variables.
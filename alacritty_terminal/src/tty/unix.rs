@@ -1,26 +1,26 @@
 //! TTY related functionality.
 
 use std::ffi::CStr;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{Error, ErrorKind, Read, Result};
 use std::mem::MaybeUninit;
 use std::os::fd::OwnedFd;
+use std::os::unix::fs::OpenOptionsExt;
 use std::os::unix::io::AsRawFd;
 use std::os::unix::net::UnixStream;
 use std::os::unix::process::CommandExt;
-#[cfg(target_os = "macos")]
 use std::path::Path;
 use std::process::{Child, Command};
 use std::sync::Arc;
 use std::{env, ptr};
 
-use libc::{F_GETFL, F_SETFL, O_NONBLOCK, TIOCSCTTY, c_int, fcntl};
+use libc::{F_GETFL, F_SETFL, O_NONBLOCK, TIOCSCTTY, c_int, fcntl, pid_t};
 use log::error;
 use polling::{Event, PollMode, Poller};
 use rustix_openpty::openpty;
-use rustix_openpty::rustix::termios::Winsize;
 #[cfg(any(target_os = "linux", target_os = "macos"))]
-use rustix_openpty::rustix::termios::{self, InputModes, OptionalActions};
+use rustix_openpty::rustix::termios::InputModes;
+use rustix_openpty::rustix::termios::{self, ControlModes, OptionalActions, Winsize};
 use signal_hook::low_level::{pipe as signal_pipe, unregister as unregister_signal};
 use signal_hook::{SigId, consts as sigconsts};
 
@@ -101,20 +101,29 @@ fn get_pw_entry(buf: &mut [i8; 1024]) -> Result<Passwd<'_>> {
 }
 
 pub struct Pty {
-    child: Child,
+    child: Option<Child>,
     file: File,
     signals: UnixStream,
     sig_id: SigId,
 }
 
 impl Pty {
-    pub fn child(&self) -> &Child {
-        &self.child
+    /// Get the child process, unless the PTY was attached to an existing terminal.
+    pub fn child(&self) -> Option<&Child> {
+        self.child.as_ref()
     }
 
     pub fn file(&self) -> &File {
         &self.file
     }
+
+    /// Get the ID of the PTY's foreground process group.
+    pub fn foreground_process_group(&self) -> Result<pid_t> {
+        match unsafe { libc::tcgetpgrp(self.file.as_raw_fd()) } {
+            -1 => Err(Error::last_os_error()),
+            pgid => Ok(pgid),
+        }
+    }
 }
 
 /// User information that is required for a new shell session.
@@ -273,14 +282,7 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
     }
 
     // Prepare signal handling before spawning child.
-    let (signals, sig_id) = {
-        let (sender, recv) = UnixStream::pair()?;
-
-        // Register the recv end of the pipe for SIGCHLD.
-        let sig_id = signal_pipe::register(sigconsts::SIGCHLD, sender)?;
-        recv.set_nonblocking(true)?;
-        (recv, sig_id)
-    };
+    let (signals, sig_id) = child_signals()?;
 
     match builder.spawn() {
         Ok(child) => {
@@ -290,7 +292,7 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
                 set_nonblocking(master_fd);
             }
 
-            Ok(Pty { child, file: File::from(master), signals, sig_id })
+            Ok(Pty { child: Some(child), file: File::from(master), signals, sig_id })
         },
         Err(err) => Err(Error::new(
             err.kind(),
@@ -303,17 +305,58 @@ pub fn from_fd(config: &Options, window_id: u64, master: OwnedFd, slave: OwnedFd
     }
 }
 
+/// Create a TTY from a file descriptor of an existing terminal session, like the master side of
+/// a PTY handed over by another process.
+///
+/// No child process is spawned, so the terminal exits once the file descriptor hangs up.
+pub fn attach(fd: OwnedFd) -> Result<Pty> {
+    let (signals, sig_id) = child_signals()?;
+
+    unsafe {
+        set_nonblocking(fd.as_raw_fd());
+    }
+
+    Ok(Pty { child: None, file: File::from(fd), signals, sig_id })
+}
+
+/// Create a TTY from a serial device, like `/dev/ttyUSB0`, using raw mode and the given baud rate.
+pub fn serial(path: &Path, baud: u32) -> Result<Pty> {
+    let file = OpenOptions::new().read(true).write(true).custom_flags(libc::O_NOCTTY).open(path)?;
+
+    let mut termios = termios::tcgetattr(&file)?;
+    termios.make_raw();
+    termios.control_modes.insert(ControlModes::CLOCAL | ControlModes::CREAD);
+    termios.set_speed(baud)?;
+    termios::tcsetattr(&file, OptionalActions::Now, &termios)?;
+
+    attach(file.into())
+}
+
+/// Register a pipe receiving SIGCHLD notifications.
+fn child_signals() -> Result<(UnixStream, SigId)> {
+    let (sender, recv) = UnixStream::pair()?;
+
+    // Register the recv end of the pipe for SIGCHLD.
+    let sig_id = signal_pipe::register(sigconsts::SIGCHLD, sender)?;
+    recv.set_nonblocking(true)?;
+    Ok((recv, sig_id))
+}
+
 impl Drop for Pty {
     fn drop(&mut self) {
         // Make sure the PTY is terminated properly.
-        unsafe {
-            libc::kill(self.child.id() as i32, libc::SIGHUP);
+        if let Some(child) = &self.child {
+            unsafe {
+                libc::kill(child.id() as i32, libc::SIGHUP);
+            }
         }
 
         // Clear signal-hook handler.
         unregister_signal(self.sig_id);
 
-        let _ = self.child.wait();
+        if let Some(child) = &mut self.child {
+            let _ = child.wait();
+        }
     }
 }
 
@@ -389,7 +432,7 @@ impl EventedPty for Pty {
         }
 
         // Match on the child process.
-        match self.child.try_wait() {
+        match self.child.as_mut()?.try_wait() {
             Err(err) => {
                 error!("Error checking child process termination: {err}");
                 None
@@ -398,6 +441,19 @@ impl EventedPty for Pty {
             Ok(exit_status) => Some(ChildEvent::Exited(exit_status.and_then(|s| s.code()))),
         }
     }
+
+    #[inline]
+    fn has_child(&self) -> bool {
+        self.child.is_some()
+    }
+
+    fn signal_foreground(&self, signal: i32) -> Result<()> {
+        let pgid = self.foreground_process_group()?;
+        match unsafe { libc::killpg(pgid, signal) } {
+            -1 => Err(Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
 }
 
 impl OnResize for Pty {
@@ -410,7 +466,8 @@ impl OnResize for Pty {
 
         let res = unsafe { libc::ioctl(self.file.as_raw_fd(), libc::TIOCSWINSZ, &win as *const _) };
 
-        if res < 0 {
+        // Attached file descriptors aren't necessarily terminals which can be resized.
+        if res < 0 && self.child.is_some() {
             die!("ioctl TIOCSWINSZ failed: {}", Error::last_os_error());
         }
     }
@@ -443,3 +500,11 @@ fn test_get_pw_entry() {
     let mut buf: [i8; 1024] = [0; 1024];
     let _pw = get_pw_entry(&mut buf).unwrap();
 }
+
+#[test]
+fn test_attach_without_child() {
+    let pty = openpty(None, None).unwrap();
+    let attached = attach(pty.controller).unwrap();
+    assert!(attached.child().is_none());
+    assert!(!attached.has_child());
+}